@@ -1,90 +1,26 @@
-use std::{
-    error::Error,
-    fs::{metadata, File},
-    io::Read,
-};
+use std::{error::Error, path::Path};
 
 use plotters::prelude::*;
 use rand::{seq::index::sample, thread_rng};
 use tenso_rs::operation::{input::InputPlaceholder, Operation};
-use tenso_rs::optim::{sgd::SGDOptimizerRunner, Optimizer};
-use tenso_rs::{matrix::Matrix, optim::RunningOptimizer};
+use tenso_rs::optim::{adam::AdamOptimizerRunner, Optimizer};
+use tenso_rs::{
+    data::idx::IdxFile,
+    matrix::{Axis, Matrix},
+    optim::RunningOptimizer,
+};
 
-fn linear(input: &Operation, in_size: usize, out_size: usize) -> Operation {
-    let weights = Matrix::randn(out_size, in_size, 0.0, 1.0).as_variable();
-    let biases = Matrix::randn(out_size, 1, 0.0, 1.0).as_variable();
+fn linear(input: &Operation, in_size: usize, out_size: usize, name: &str) -> Operation {
+    let weights =
+        Matrix::randn(out_size, in_size, 0.0, 1.0).as_named_variable(&format!("{}.weight", name));
+    let biases = Matrix::randn(out_size, 1, 0.0, 1.0).as_named_variable(&format!("{}.bias", name));
 
     weights.mmul(input.clone()) + biases
 }
 
-fn read_binary_file(filename: &str) -> Vec<u8> {
-    match File::open(filename) {
-        Ok(mut file) => {
-            let file_len = match metadata(filename) {
-                Ok(meta) => meta.len(),
-                Err(_) => 0,
-            };
-
-            let mut buffer: Vec<u8> = vec![0; file_len as usize];
-            file.read(buffer.as_mut_slice()).expect("Buffer Overflow");
-
-            buffer
-        }
-        Err(_) => Vec::new(),
-    }
-}
-
-fn to_vectors(raw_data: Vec<u8>, len: usize) -> Vec<Matrix> {
-    debug_assert_eq!(raw_data.len() % len, 0);
-
-    let mut matrices = Vec::<Matrix>::with_capacity(raw_data.len() / len);
-
-    for chunk in raw_data.chunks(len) {
-        let mat = Matrix::new(len, 1, chunk.iter().map(|v| *v as f32).collect());
-        matrices.push(mat);
-    }
-
-    matrices
-}
-
-fn to_one_hot_vectors(raw_data: Vec<u8>, len: usize) -> Vec<Matrix> {
-    let mut matrices = Vec::<Matrix>::with_capacity(raw_data.len() / len);
-
-    for value in raw_data {
-        let mut one_hot: Vec<f32> = vec![0.0; len];
-        one_hot[value as usize] = 1.0;
-
-        let mat = Matrix::new(len, 1, one_hot);
-        matrices.push(mat);
-    }
-
-    matrices
-}
-
-fn is_accurate(y: &Matrix, label: &Matrix) -> bool {
-    let mut max_index: usize = 0;
-    let mut max_val: f32 = 0.0;
-    let mut max_real_index = 0;
-
-    for i in 0..10 {
-        let label_val = label[i][0];
-        if label_val == 1.0 {
-            max_real_index = i;
-        }
-
-        let val = y[i][0];
-        if val > max_val {
-            max_index = i;
-            max_val = val;
-        }
-    }
-
-    max_index == max_real_index
-}
-
 fn plot_data(losses: Vec<f32>, accuracies: Vec<f32>) -> Result<(), Box<dyn Error>> {
     let root = BitMapBackend::new("examples/mnist_result.png", (640, 480)).into_drawing_area();
-    root.fill(&WHITE);
+    root.fill(&WHITE)?;
     let root = root.margin(10, 10, 10, 10);
 
     let mut chart = ChartBuilder::on(&root)
@@ -111,16 +47,14 @@ fn plot_data(losses: Vec<f32>, accuracies: Vec<f32>) -> Result<(), Box<dyn Error
 
     chart.draw_series(LineSeries::new(
         (0..losses.len())
-            .zip(losses.into_iter())
-            .into_iter()
+            .zip(losses)
             .map(|(index, loss)| (index as f32, loss)),
         &RED,
     ))?;
 
     chart.draw_series(LineSeries::new(
         (0..accuracies.len())
-            .zip(accuracies.into_iter())
-            .into_iter()
+            .zip(accuracies)
             .map(|(index, accuracy)| (index as f32, accuracy)),
         &BLUE,
     ))?;
@@ -131,28 +65,32 @@ fn plot_data(losses: Vec<f32>, accuracies: Vec<f32>) -> Result<(), Box<dyn Error
 fn main() {
     const SAMPLE_SIZE: usize = 128;
 
-    let mut image_data = read_binary_file("data/mnist/train-images-idx3-ubyte");
-    image_data.drain(0..16);
-
-    let mut label_data = read_binary_file("data/mnist/train-labels-idx1-ubyte");
-    label_data.drain(0..8);
+    let images = IdxFile::open("data/mnist/train-images-idx3-ubyte").unwrap();
+    let labels = IdxFile::open("data/mnist/train-labels-idx1-ubyte").unwrap();
+    assert_eq!(images.len(), labels.len());
 
-    let in_size: usize = 28 * 28;
+    let in_size: usize = images.sample_size();
     let out_size: usize = 10;
 
-    let inputs: Vec<Matrix> = to_vectors(image_data, in_size);
-    let labels: Vec<Matrix> = to_one_hot_vectors(label_data, out_size);
+    let inputs: Vec<Matrix> = images.samples().collect();
+    let labels: Vec<Matrix> = labels.one_hot(out_size).unwrap();
 
-    let mut input_ph = InputPlaceholder::new();
-    let mut label_ph = InputPlaceholder::new();
+    let mut input_ph = InputPlaceholder::named("image");
+    let mut label_ph = InputPlaceholder::named("label");
 
-    let mut net = linear(&input_ph, in_size, 16).sigmoid();
-    net = linear(&net, 16, out_size).sigmoid();
+    let mut net = linear(&input_ph, in_size, 16, "hidden").sigmoid();
+    net = linear(&net, 16, out_size, "output");
+
+    // Resume from the previous run when it left a checkpoint.
+    const CHECKPOINT: &str = "examples/mnist.ckpt";
+    if Path::new(CHECKPOINT).exists() {
+        net.load(CHECKPOINT).unwrap();
+    }
 
-    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(0.01));
+    let mut optim = RunningOptimizer::new(AdamOptimizerRunner::new(0.001));
     net.add_to_optimizer(&mut optim);
 
-    let mut loss_f = (label_ph.clone() - net.clone()).pow(2.0).sum();
+    let mut loss_f = net.clone().cross_entropy(label_ph.clone());
 
     let mut losses: Vec<f32> = Vec::new();
     let mut accuracies: Vec<f32> = Vec::new();
@@ -173,7 +111,7 @@ fn main() {
             loss_sum += loss[0][0];
 
             let net_out = net.get_output();
-            if is_accurate(&net_out, label) {
+            if net_out.argmax(Axis::Height) == label.argmax(Axis::Height) {
                 n_accurate += 1;
             }
 
@@ -181,6 +119,7 @@ fn main() {
         }
 
         optim.step();
+        optim.zero_grad();
 
         losses.push(loss_sum / SAMPLE_SIZE as f32);
         accuracies.push(n_accurate as f32 / SAMPLE_SIZE as f32);
@@ -189,5 +128,6 @@ fn main() {
         println!("Loss: {}\n", losses.last().unwrap());
     }
 
+    net.save(CHECKPOINT).unwrap();
     plot_data(losses, accuracies).unwrap();
 }
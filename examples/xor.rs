@@ -47,6 +47,7 @@ fn main() {
         }
 
         optim.step();
+        optim.zero_grad();
         println!("Loss: {}", loss_sum);
     }
 }
@@ -0,0 +1,176 @@
+//! IDX files, the format the MNIST dataset is distributed in.
+//!
+//! A file starts with two zero bytes, a byte giving the element type and a byte giving the number
+//! of dimensions, followed by every dimension as a big-endian `u32` and the big-endian elements.
+//! The first dimension indexes the samples.
+
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use crate::{error::TensoError, io::read_bytes, matrix::Matrix};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementType {
+    U8,
+    I8,
+    I16,
+    I32,
+    F32,
+    F64,
+}
+
+impl ElementType {
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x08 => Some(ElementType::U8),
+            0x09 => Some(ElementType::I8),
+            0x0b => Some(ElementType::I16),
+            0x0c => Some(ElementType::I32),
+            0x0d => Some(ElementType::F32),
+            0x0e => Some(ElementType::F64),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ElementType::U8 | ElementType::I8 => 1,
+            ElementType::I16 => 2,
+            ElementType::I32 | ElementType::F32 => 4,
+            ElementType::F64 => 8,
+        }
+    }
+
+    fn convert(self, bytes: &[u8]) -> f32 {
+        match self {
+            ElementType::U8 => bytes[0] as f32,
+            ElementType::I8 => bytes[0] as i8 as f32,
+            ElementType::I16 => i16::from_be_bytes([bytes[0], bytes[1]]) as f32,
+            ElementType::I32 => i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+            ElementType::F32 => f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            ElementType::F64 => {
+                let mut element = [0; 8];
+                element.copy_from_slice(bytes);
+                f64::from_be_bytes(element) as f32
+            }
+        }
+    }
+}
+
+/// Content of an IDX file, elements converted to `f32`.
+pub struct IdxFile {
+    element_type: ElementType,
+    dimensions: Vec<usize>,
+    data: Vec<f32>,
+}
+
+impl IdxFile {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TensoError> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn read(reader: &mut impl Read) -> Result<Self, TensoError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic[0] != 0 || magic[1] != 0 {
+            return Err(TensoError::InvalidFormat {
+                reason: "not an IDX file".to_string(),
+            });
+        }
+
+        let element_type =
+            ElementType::from_code(magic[2]).ok_or_else(|| TensoError::UnsupportedDtype {
+                dtype: format!("0x{:02x}", magic[2]),
+            })?;
+        if magic[3] == 0 {
+            return Err(TensoError::UnsupportedRank { rank: 0 });
+        }
+
+        let mut dimensions = Vec::with_capacity(magic[3] as usize);
+        for _ in 0..magic[3] {
+            let mut dimension = [0; 4];
+            reader.read_exact(&mut dimension)?;
+            dimensions.push(u32::from_be_bytes(dimension) as usize);
+        }
+
+        let byte_count = dimensions
+            .iter()
+            .try_fold(element_type.size(), |count, dimension| {
+                count.checked_mul(*dimension)
+            })
+            .ok_or_else(|| TensoError::InvalidFormat {
+                reason: "data size overflows".to_string(),
+            })?;
+        let data = read_bytes(reader, byte_count)?
+            .chunks_exact(element_type.size())
+            .map(|element| element_type.convert(element))
+            .collect();
+
+        Ok(Self {
+            element_type,
+            dimensions,
+            data,
+        })
+    }
+
+    pub fn element_type(&self) -> ElementType {
+        self.element_type
+    }
+
+    pub fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+
+    /// Number of samples, the first dimension.
+    pub fn len(&self) -> usize {
+        self.dimensions[0]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of elements in a sample, the product of all dimensions but the first.
+    pub fn sample_size(&self) -> usize {
+        self.dimensions[1..].iter().product()
+    }
+
+    /// Every sample flattened into a column vector, a `28x28` image gives a `784x1` matrix.
+    pub fn samples(&self) -> impl Iterator<Item = Matrix> + '_ {
+        let sample_size = self.sample_size();
+        (0..self.len()).map(move |index| {
+            Matrix::new(
+                sample_size,
+                1,
+                self.data[index * sample_size..(index + 1) * sample_size].to_vec(),
+            )
+        })
+    }
+
+    /// Every label of a one dimensional file as a one-hot column vector of `classes` elements.
+    pub fn one_hot(&self, classes: usize) -> Result<Vec<Matrix>, TensoError> {
+        if self.dimensions.len() != 1 {
+            return Err(TensoError::UnsupportedRank {
+                rank: self.dimensions.len(),
+            });
+        }
+
+        self.data
+            .iter()
+            .map(|label| {
+                if label.fract() != 0.0 || *label < 0.0 || *label as usize >= classes {
+                    return Err(TensoError::InvalidFormat {
+                        reason: format!("label {} is not one of {} classes", label, classes),
+                    });
+                }
+
+                let mut one_hot = Matrix::zeros(classes, 1);
+                one_hot[*label as usize][0] = 1.0;
+                Ok(one_hot)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,3 @@
+//! Readers for dataset files.
+
+pub mod idx;
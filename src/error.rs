@@ -0,0 +1,116 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TensoError {
+    /// The inputs of `op` have shapes it can not combine.
+    ShapeMismatch {
+        op: &'static str,
+        left: Vec<usize>,
+        right: Vec<usize>,
+    },
+    /// A custom `op` returned a different number of gradients than it has inputs.
+    GradientCountMismatch {
+        op: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// `op` needs at least one element in its input.
+    EmptyInput { op: &'static str },
+    /// `op` runs along a dimension its input does not have.
+    DimOutOfRange {
+        op: &'static str,
+        dim: usize,
+        rank: usize,
+    },
+    /// Backward can only start from an output holding a single element.
+    NonScalarBackward { shape: Vec<usize> },
+    /// Reading or writing a file failed.
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    /// The data read is not in the expected format.
+    InvalidFormat { reason: String },
+    /// A checkpoint holds a different number of parameters than the graph.
+    ParameterCountMismatch { expected: usize, found: usize },
+    /// Stored elements of a type that can't be converted to `f32`.
+    UnsupportedDtype { dtype: String },
+    /// Stored arrays of a rank that does not fit in a matrix.
+    UnsupportedRank { rank: usize },
+    /// A named variable of the graph has no value in a state dict.
+    MissingKey { key: String },
+    /// A state dict holds a value no named variable of the graph matches.
+    UnexpectedKey { key: String },
+    /// Two named variables of the graph share the same name.
+    DuplicateKey { key: String },
+}
+
+impl Display for TensoError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TensoError::ShapeMismatch { op, left, right } => write!(
+                fmt,
+                "Shape mismatch in {}: {} and {}",
+                op,
+                format_shape(left),
+                format_shape(right)
+            ),
+            TensoError::GradientCountMismatch {
+                op,
+                expected,
+                found,
+            } => write!(
+                fmt,
+                "Gradient count mismatch in {}: expected {}, found {}",
+                op, expected, found
+            ),
+            TensoError::EmptyInput { op } => write!(fmt, "Empty input in {}", op),
+            TensoError::DimOutOfRange { op, dim, rank } => write!(
+                fmt,
+                "Dimension out of range in {}: {} for rank {}",
+                op, dim, rank
+            ),
+            TensoError::NonScalarBackward { shape } => write!(
+                fmt,
+                "Cant backpropagate a non-unit tensor: {}",
+                format_shape(shape)
+            ),
+            TensoError::Io { message, .. } => write!(fmt, "IO error: {}", message),
+            TensoError::InvalidFormat { reason } => write!(fmt, "Invalid format: {}", reason),
+            TensoError::ParameterCountMismatch { expected, found } => write!(
+                fmt,
+                "Parameter count mismatch: expected {}, found {}",
+                expected, found
+            ),
+            TensoError::UnsupportedDtype { dtype } => write!(fmt, "Unsupported dtype: {}", dtype),
+            TensoError::UnsupportedRank { rank } => write!(fmt, "Unsupported rank: {}", rank),
+            TensoError::MissingKey { key } => write!(fmt, "Missing key: {}", key),
+            TensoError::UnexpectedKey { key } => write!(fmt, "Unexpected key: {}", key),
+            TensoError::DuplicateKey { key } => write!(fmt, "Duplicate key: {}", key),
+        }
+    }
+}
+
+impl Error for TensoError {}
+
+/// Dimensions joined with `x`, `2x3x4`, rank 0 shapes are written `scalar`.
+fn format_shape(shape: &[usize]) -> String {
+    if shape.is_empty() {
+        return "scalar".to_string();
+    }
+
+    shape
+        .iter()
+        .map(|dim| dim.to_string())
+        .collect::<Vec<_>>()
+        .join("x")
+}
+
+impl From<io::Error> for TensoError {
+    fn from(err: io::Error) -> Self {
+        TensoError::Io {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Blocked matrix multiplication kernels on row-major slices.
+//!
+//! The output is split in blocks of `BLOCK` rows, each block is computed independently so they
+//! can run in parallel when the `rayon` feature is enabled.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Side of the square tiles, 64 rows of 64 floats fit in the L1 cache of most CPUs.
+const BLOCK: usize = 64;
+
+/// Shape of a multiplication, `lhs` is `m x k` and the output `m x n`.
+#[derive(Clone, Copy)]
+pub struct Dims {
+    pub m: usize,
+    pub k: usize,
+    pub n: usize,
+}
+
+/// `out = lhs * rhs` with `rhs` a `k x n` matrix.
+pub fn multiply(lhs: &[f32], rhs: &[f32], out: &mut [f32], dims: Dims) {
+    debug_assert_eq!(out.len(), dims.m * dims.n);
+
+    for_each_row_block(out, dims, |first_row, out_block| {
+        let rows = out_block.len() / dims.n;
+        for k0 in (0..dims.k).step_by(BLOCK) {
+            let k1 = (k0 + BLOCK).min(dims.k);
+            for j0 in (0..dims.n).step_by(BLOCK) {
+                let j1 = (j0 + BLOCK).min(dims.n);
+                for i in 0..rows {
+                    let lhs_row = &lhs[(first_row + i) * dims.k..][..dims.k];
+                    let out_row = &mut out_block[i * dims.n..][j0..j1];
+                    for (p, lhs_value) in lhs_row.iter().enumerate().take(k1).skip(k0) {
+                        let rhs_row = &rhs[p * dims.n..][j0..j1];
+                        for (out_value, rhs_value) in out_row.iter_mut().zip(rhs_row) {
+                            *out_value += lhs_value * rhs_value;
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// `out = lhs * rhs^T` with `rhs` a `n x k` matrix, both operands are read along their rows.
+pub fn multiply_transposed(lhs: &[f32], rhs: &[f32], out: &mut [f32], dims: Dims) {
+    debug_assert_eq!(out.len(), dims.m * dims.n);
+
+    for_each_row_block(out, dims, |first_row, out_block| {
+        let rows = out_block.len() / dims.n;
+        for j0 in (0..dims.n).step_by(BLOCK) {
+            let j1 = (j0 + BLOCK).min(dims.n);
+            for i in 0..rows {
+                let lhs_row = &lhs[(first_row + i) * dims.k..][..dims.k];
+                for j in j0..j1 {
+                    let rhs_row = &rhs[j * dims.k..][..dims.k];
+                    out_block[i * dims.n + j] = dot(lhs_row, rhs_row);
+                }
+            }
+        }
+    });
+}
+
+/// `out = lhs^T * rhs` with `lhs` a `k x m` and `rhs` a `k x n` matrix, without building the
+/// transpose of `lhs`.
+pub fn transposed_multiply(lhs: &[f32], rhs: &[f32], out: &mut [f32], dims: Dims) {
+    debug_assert_eq!(out.len(), dims.m * dims.n);
+
+    for_each_row_block(out, dims, |first_row, out_block| {
+        let rows = out_block.len() / dims.n;
+        for k0 in (0..dims.k).step_by(BLOCK) {
+            let k1 = (k0 + BLOCK).min(dims.k);
+            for j0 in (0..dims.n).step_by(BLOCK) {
+                let j1 = (j0 + BLOCK).min(dims.n);
+                for i in 0..rows {
+                    let out_row = &mut out_block[i * dims.n..][j0..j1];
+                    for p in k0..k1 {
+                        let lhs_value = lhs[p * dims.m + first_row + i];
+                        let rhs_row = &rhs[p * dims.n..][j0..j1];
+                        for (out_value, rhs_value) in out_row.iter_mut().zip(rhs_row) {
+                            *out_value += lhs_value * rhs_value;
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Dot product over `LANES` independent sums, which lets the compiler vectorize it.
+fn dot(lhs: &[f32], rhs: &[f32]) -> f32 {
+    const LANES: usize = 8;
+
+    let mut sums = [0.0; LANES];
+    let lhs_chunks = lhs.chunks_exact(LANES);
+    let rhs_chunks = rhs.chunks_exact(LANES);
+    let remainder: f32 = lhs_chunks
+        .remainder()
+        .iter()
+        .zip(rhs_chunks.remainder())
+        .map(|(lhs_value, rhs_value)| lhs_value * rhs_value)
+        .sum();
+
+    for (lhs_chunk, rhs_chunk) in lhs_chunks.zip(rhs_chunks) {
+        for lane in 0..LANES {
+            sums[lane] += lhs_chunk[lane] * rhs_chunk[lane];
+        }
+    }
+
+    sums.iter().sum::<f32>() + remainder
+}
+
+#[cfg(not(feature = "rayon"))]
+fn for_each_row_block(out: &mut [f32], dims: Dims, kernel: impl Fn(usize, &mut [f32])) {
+    if dims.n == 0 {
+        return;
+    }
+
+    for (index, out_block) in out.chunks_mut(BLOCK * dims.n).enumerate() {
+        kernel(index * BLOCK, out_block);
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn for_each_row_block(out: &mut [f32], dims: Dims, kernel: impl Fn(usize, &mut [f32]) + Sync) {
+    if dims.n == 0 {
+        return;
+    }
+
+    out.par_chunks_mut(BLOCK * dims.n)
+        .enumerate()
+        .for_each(|(index, out_block)| kernel(index * BLOCK, out_block));
+}
@@ -0,0 +1,19 @@
+//! Reading helpers shared by the file format readers.
+
+use std::io::{self, Read};
+
+use crate::error::TensoError;
+
+/// Reads exactly `len` bytes.
+///
+/// Lengths come from file headers, reading through `take` only allocates as much as the reader
+/// actually holds, so a corrupted header fails with `UnexpectedEof` instead of a huge allocation.
+pub fn read_bytes(reader: &mut impl Read, len: usize) -> Result<Vec<u8>, TensoError> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(bytes)
+}
@@ -0,0 +1,67 @@
+//! Elementwise map, zip and reduce kernels on slices.
+//!
+//! With the `parallel` feature slices of at least `PARALLEL_THRESHOLD` elements are split across
+//! threads, smaller ones stay on the serial path where spawning tasks would cost more than it saves.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Number of elements below which a kernel always runs serially.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+/// Elements handed to a single task, large enough to amortize the scheduling.
+#[cfg(feature = "parallel")]
+const CHUNK: usize = 1 << 12;
+
+pub fn map(data: &[f32], f: impl Fn(f32) -> f32 + Sync + Send) -> Vec<f32> {
+    #[cfg(feature = "parallel")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        return data.par_iter().with_min_len(CHUNK).map(|v| f(*v)).collect();
+    }
+
+    data.iter().map(|v| f(*v)).collect()
+}
+
+pub fn map_in_place(data: &mut [f32], f: impl Fn(f32) -> f32 + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        data.par_iter_mut()
+            .with_min_len(CHUNK)
+            .for_each(|v| *v = f(*v));
+        return;
+    }
+
+    data.iter_mut().for_each(|v| *v = f(*v));
+}
+
+pub fn zip_map(left: &[f32], right: &[f32], f: impl Fn(f32, f32) -> f32 + Sync + Send) -> Vec<f32> {
+    assert_eq!(left.len(), right.len(), "Slice lengths do not match!");
+
+    #[cfg(feature = "parallel")]
+    if left.len() >= PARALLEL_THRESHOLD {
+        return left
+            .par_iter()
+            .zip(right.par_iter())
+            .with_min_len(CHUNK)
+            .map(|(l, r)| f(*l, *r))
+            .collect();
+    }
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(l, r)| f(*l, *r))
+        .collect()
+}
+
+pub fn sum(data: &[f32]) -> f32 {
+    #[cfg(feature = "parallel")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        return data
+            .par_chunks(CHUNK)
+            .map(|chunk| chunk.iter().sum::<f32>())
+            .sum();
+    }
+
+    data.iter().sum()
+}
@@ -1,3 +1,14 @@
+// Node constructors hand back the type erased `Operation` instead of `Self`.
+#![allow(clippy::new_ret_no_self)]
+
+pub mod data;
+pub mod error;
+mod gemm;
+mod io;
+mod kernel;
 pub mod matrix;
+pub mod npy;
 pub mod operation;
 pub mod optim;
+pub mod safetensors;
+pub mod tensor;
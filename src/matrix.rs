@@ -7,7 +7,18 @@ use std::{
 
 use rand::{distributions::Normal, Rng};
 
-#[derive(Clone)]
+use crate::{gemm, kernel};
+
+/// Dimension of a matrix an operation is applied along.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// Along the height, every column is a separate lane.
+    Height,
+    /// Along the width, every row is a separate lane.
+    Width,
+}
+
+#[derive(Clone, PartialEq)]
 pub struct Matrix {
     height: usize,
     width: usize,
@@ -43,7 +54,7 @@ impl Matrix {
             height,
             width,
             data: (0..height * width)
-                .map(|_| rng.sample(&normal) as f32)
+                .map(|_| rng.sample(normal) as f32)
                 .collect(),
         }
     }
@@ -54,6 +65,31 @@ impl Matrix {
         self.data.iter_mut().for_each(|v| *v = 0.0);
     }
 
+    /// Replaces every element `v` with `f(v)` in place.
+    pub fn apply(&mut self, f: impl Fn(f32) -> f32 + Sync + Send) {
+        kernel::map_in_place(&mut self.data, f);
+    }
+
+    /// New matrix with `f` applied to every element.
+    pub fn map(&self, f: impl Fn(f32) -> f32 + Sync + Send) -> Matrix {
+        Matrix::new(self.height, self.width, kernel::map(&self.data, f))
+    }
+
+    /// New matrix with `f` applied to the elements of both matrices pairwise, the shapes must match.
+    pub fn zip_map(&self, other: &Matrix, f: impl Fn(f32, f32) -> f32 + Sync + Send) -> Matrix {
+        assert_eq!(self.shape(), other.shape(), "Matrix shapes do not match!");
+
+        Matrix::new(
+            self.height,
+            self.width,
+            kernel::zip_map(&self.data, &other.data, f),
+        )
+    }
+
+    pub fn sum(&self) -> f32 {
+        kernel::sum(&self.data)
+    }
+
     pub fn set(&mut self, other: Matrix) {
         self.width = other.width;
         self.height = other.height;
@@ -68,6 +104,95 @@ impl Matrix {
         self.width
     }
 
+    pub fn shape(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut result = Matrix::zeros(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                result[x][y] = self[y][x];
+            }
+        }
+
+        result
+    }
+
+    /// Matrix product `self * rhs`.
+    pub fn mmul(&self, rhs: &Matrix) -> Matrix {
+        assert_eq!(self.width, rhs.height, "Matrix shapes do not match!");
+
+        let mut result = Matrix::zeros(self.height, rhs.width);
+        gemm::multiply(
+            &self.data,
+            &rhs.data,
+            &mut result.data,
+            gemm::Dims {
+                m: self.height,
+                k: self.width,
+                n: rhs.width,
+            },
+        );
+
+        result
+    }
+
+    /// Matrix product `self * rhs^T`, without building the transpose of `rhs`.
+    pub fn mmul_transposed(&self, rhs: &Matrix) -> Matrix {
+        assert_eq!(self.width, rhs.width, "Matrix shapes do not match!");
+
+        let mut result = Matrix::zeros(self.height, rhs.height);
+        gemm::multiply_transposed(
+            &self.data,
+            &rhs.data,
+            &mut result.data,
+            gemm::Dims {
+                m: self.height,
+                k: self.width,
+                n: rhs.height,
+            },
+        );
+
+        result
+    }
+
+    /// Matrix product `self^T * rhs`, without building the transpose of `self`.
+    pub fn transposed_mmul(&self, rhs: &Matrix) -> Matrix {
+        assert_eq!(self.height, rhs.height, "Matrix shapes do not match!");
+
+        let mut result = Matrix::zeros(self.width, rhs.width);
+        gemm::transposed_multiply(
+            &self.data,
+            &rhs.data,
+            &mut result.data,
+            gemm::Dims {
+                m: self.width,
+                k: self.height,
+                n: rhs.width,
+            },
+        );
+
+        result
+    }
+
+    /// Same row-major data viewed as a `height` x `width` matrix.
+    pub fn reshape(self, height: usize, width: usize) -> Matrix {
+        assert_eq!(
+            self.height * self.width,
+            height * width,
+            "Reshape does not preserve the size!"
+        );
+
+        Matrix::new(height, width, self.data)
+    }
+
+    /// Row-major data as a column vector.
+    pub fn flatten(self) -> Matrix {
+        let len = self.data.len();
+        self.reshape(len, 1)
+    }
+
     pub fn chain_data<T>(&self, accessor: impl Fn(Iter<f32>) -> T) -> T {
         accessor(self.data.iter())
     }
@@ -79,19 +204,124 @@ impl Matrix {
     ) -> T {
         accessor(self.data.iter().zip(other.data.iter()))
     }
+
+    /// Number of lanes along `axis`, the columns for `Axis::Height` and the rows for `Axis::Width`.
+    pub fn lane_count(&self, axis: Axis) -> usize {
+        match axis {
+            Axis::Height => self.width,
+            Axis::Width => self.height,
+        }
+    }
+
+    pub fn lane(&self, axis: Axis, index: usize) -> Vec<f32> {
+        match axis {
+            Axis::Height => (0..self.height).map(|y| self[y][index]).collect(),
+            Axis::Width => self[index].to_vec(),
+        }
+    }
+
+    pub fn set_lane(&mut self, axis: Axis, index: usize, values: &[f32]) {
+        match axis {
+            Axis::Height => {
+                for (y, value) in values.iter().enumerate() {
+                    self[y][index] = *value;
+                }
+            }
+            Axis::Width => self[index].copy_from_slice(values),
+        }
+    }
+
+    /// Index of the largest element of every lane along `axis`, the first one on ties.
+    pub fn argmax(&self, axis: Axis) -> Vec<usize> {
+        (0..self.lane_count(axis))
+            .map(|index| {
+                let lane = self.lane(axis, index);
+                (0..lane.len()).fold(0, |best, i| if lane[i] > lane[best] { i } else { best })
+            })
+            .collect()
+    }
+
+    /*------------------------------------------------------*/
+
+    /// Shape both matrices broadcast to, NumPy style.
+    ///
+    /// Each dimension must either be equal in both matrices or be 1 in one of them.
+    pub fn broadcast_shape(&self, other: &Matrix) -> Option<(usize, usize)> {
+        fn broadcast_dim(dim_left: usize, dim_right: usize) -> Option<usize> {
+            if dim_left == dim_right || dim_right == 1 {
+                Some(dim_left)
+            } else if dim_left == 1 {
+                Some(dim_right)
+            } else {
+                None
+            }
+        }
+
+        Some((
+            broadcast_dim(self.height, other.height)?,
+            broadcast_dim(self.width, other.width)?,
+        ))
+    }
+
+    /// Applies `f` elementwise after broadcasting both matrices to their common shape.
+    pub fn broadcast_zip(
+        &self,
+        other: &Matrix,
+        f: impl Fn(f32, f32) -> f32 + Sync + Send,
+    ) -> Matrix {
+        if self.height == other.height && self.width == other.width {
+            return self.zip_map(other, f);
+        }
+
+        let (height, width) = self
+            .broadcast_shape(other)
+            .expect("Cant broadcast matrices of incompatible shapes!");
+
+        let mut result = Matrix::zeros(height, width);
+        for y in 0..height {
+            let row_left = &self[y % self.height];
+            let row_right = &other[y % other.height];
+            for x in 0..width {
+                result[y][x] = f(row_left[x % self.width], row_right[x % other.width]);
+            }
+        }
+
+        result
+    }
+
+    /// Sums a broadcast matrix back down to `height` x `width`, the reverse of broadcasting.
+    pub fn sum_to_shape(&self, height: usize, width: usize) -> Matrix {
+        if self.height == height && self.width == width {
+            return self.clone();
+        }
+
+        assert!(
+            (height == self.height || height == 1) && (width == self.width || width == 1),
+            "Matrix can not be summed to that shape!"
+        );
+
+        let mut result = Matrix::zeros(height, width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                result[y % height][x % width] += self[y][x];
+            }
+        }
+
+        result
+    }
 }
 
 impl Index<usize> for Matrix {
     type Output = [f32];
 
-    fn index<'a>(&'a self, i: usize) -> &'a [f32] {
+    fn index(&self, i: usize) -> &[f32] {
         let start = i * self.width;
         &self.data[start..start + self.width]
     }
 }
 
 impl IndexMut<usize> for Matrix {
-    fn index_mut<'a>(&'a mut self, i: usize) -> &'a mut [f32] {
+    fn index_mut(&mut self, i: usize) -> &mut [f32] {
         let start = i * self.width;
         &mut self.data[start..start + self.width]
     }
@@ -101,13 +331,9 @@ impl Display for Matrix {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         for y in 0..self.height {
             for x in 0..self.width {
-                if let Err(e) = fmt.write_str(format!("| {:.1$}\t", self[y][x], 2).as_str()) {
-                    return Err(e);
-                }
-            }
-            if let Err(e) = fmt.write_str("|\n") {
-                return Err(e);
+                fmt.write_str(format!("| {:.1$}\t", self[y][x], 2).as_str())?;
             }
+            fmt.write_str("|\n")?;
         }
         Ok(())
     }
@@ -0,0 +1,251 @@
+//! NumPy `.npy` files and `.npz` archives of them.
+//!
+//! Arrays of rank 0 to 2 holding little-endian `float32` or `float64` in C order are supported.
+//! Rank 0 arrays become `1x1` matrices and rank 1 arrays column vectors, the same way a `Tensor`
+//! converts to a `Matrix`. Matrices are always written as rank 2 `float32` arrays.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{error::TensoError, io::read_bytes, matrix::Matrix};
+
+mod zip;
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// The whole preamble, magic to header end, is padded to a multiple of this.
+const ALIGNMENT: usize = 64;
+
+impl Matrix {
+    pub fn from_npy(path: impl AsRef<Path>) -> Result<Matrix, TensoError> {
+        Self::read_npy(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn to_npy(&self, path: impl AsRef<Path>) -> Result<(), TensoError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub fn read_npy(reader: &mut impl Read) -> Result<Matrix, TensoError> {
+        let mut magic = [0; 6];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a .npy file"));
+        }
+
+        let mut version = [0; 2];
+        reader.read_exact(&mut version)?;
+        let header_len = match version[0] {
+            1 => {
+                let mut bytes = [0; 2];
+                reader.read_exact(&mut bytes)?;
+                u16::from_le_bytes(bytes) as usize
+            }
+            2 | 3 => {
+                let mut bytes = [0; 4];
+                reader.read_exact(&mut bytes)?;
+                u32::from_le_bytes(bytes) as usize
+            }
+            major => return Err(invalid(&format!("unsupported .npy version {}", major))),
+        };
+
+        let text = String::from_utf8(read_bytes(reader, header_len)?)
+            .map_err(|_| invalid("header is not text"))?;
+        let header = parse_header(&text)?;
+
+        let descr = header
+            .get("descr")
+            .ok_or_else(|| invalid("header without descr"))?
+            .trim_matches(|c| c == '\'' || c == '"');
+        let element_size = match descr {
+            "<f4" => 4,
+            "<f8" => 8,
+            _ => {
+                return Err(TensoError::UnsupportedDtype {
+                    dtype: descr.to_string(),
+                })
+            }
+        };
+
+        match header.get("fortran_order").copied() {
+            Some("False") => {}
+            Some("True") => return Err(invalid("Fortran order is not supported")),
+            _ => return Err(invalid("header without fortran_order")),
+        }
+
+        let shape = parse_shape(
+            header
+                .get("shape")
+                .ok_or_else(|| invalid("header without shape"))?,
+        )?;
+        let (height, width) = match shape.as_slice() {
+            [] => (1, 1),
+            [len] => (*len, 1),
+            [height, width] => (*height, *width),
+            _ => return Err(TensoError::UnsupportedRank { rank: shape.len() }),
+        };
+
+        let byte_count = height
+            .checked_mul(width)
+            .and_then(|len| len.checked_mul(element_size))
+            .ok_or_else(|| invalid("data size overflows"))?;
+        let bytes = read_bytes(reader, byte_count)?;
+        let data = if element_size == 4 {
+            bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect()
+        } else {
+            bytes
+                .chunks_exact(8)
+                .map(|chunk| {
+                    let mut element = [0; 8];
+                    element.copy_from_slice(chunk);
+                    f64::from_le_bytes(element) as f32
+                })
+                .collect()
+        };
+
+        Ok(Matrix::new(height, width, data))
+    }
+
+    pub fn write_npy(&self, writer: &mut impl Write) -> Result<(), TensoError> {
+        let mut header = format!(
+            "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, {}), }}",
+            self.height(),
+            self.width()
+        );
+        // Magic, version and the header length take 10 bytes, the header ends with a newline.
+        let padding = (ALIGNMENT - (10 + header.len() + 1) % ALIGNMENT) % ALIGNMENT;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+
+        let bytes: Vec<u8> =
+            self.chain_data(|data_iter| data_iter.flat_map(|v| v.to_le_bytes()).collect());
+        writer.write_all(&bytes)?;
+
+        Ok(())
+    }
+}
+
+/// Reads every array of an `.npz` archive, by name without the `.npy` extension.
+///
+/// Only archives written by `numpy.savez` are supported, `numpy.savez_compressed` ones are not.
+pub fn load_npz(path: impl AsRef<Path>) -> Result<BTreeMap<String, Matrix>, TensoError> {
+    let archive = fs::read(path)?;
+
+    zip::read_entries(&archive)?
+        .into_iter()
+        .map(|(name, data)| {
+            let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            Ok((name, Matrix::read_npy(&mut &data[..])?))
+        })
+        .collect()
+}
+
+/// Writes the matrices to an uncompressed `.npz` archive, `numpy.load` reads them by name.
+pub fn save_npz(
+    path: impl AsRef<Path>,
+    matrices: &BTreeMap<String, Matrix>,
+) -> Result<(), TensoError> {
+    let mut entries = Vec::with_capacity(matrices.len());
+    for (name, matrix) in matrices {
+        let mut data = Vec::new();
+        matrix.write_npy(&mut data)?;
+        entries.push((format!("{}.npy", name), data));
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    zip::write_entries(&mut writer, &entries)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+fn invalid(reason: &str) -> TensoError {
+    TensoError::InvalidFormat {
+        reason: reason.to_string(),
+    }
+}
+
+/// Entries of the Python dict literal of a header, keys unquoted and values as raw text.
+fn parse_header(header: &str) -> Result<BTreeMap<&str, &str>, TensoError> {
+    let mut entries = BTreeMap::new();
+    let mut rest = header
+        .trim()
+        .strip_prefix('{')
+        .ok_or_else(|| invalid("header is not a dict"))?;
+
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix('}') {
+            if !after.trim().is_empty() {
+                return Err(invalid("trailing data after the header dict"));
+            }
+            return Ok(entries);
+        }
+
+        let (key, after) =
+            split_quoted(rest).ok_or_else(|| invalid("header key is not a string"))?;
+        let after = after
+            .trim_start()
+            .strip_prefix(':')
+            .ok_or_else(|| invalid("header key without value"))?
+            .trim_start();
+
+        let end = if after.starts_with('(') {
+            after.find(')').map(|end| end + 1)
+        } else if after.starts_with(['\'', '"']) {
+            split_quoted(after).map(|(value, _)| value.len() + 2)
+        } else {
+            after.find([',', '}'])
+        }
+        .ok_or_else(|| invalid("unterminated header value"))?;
+
+        if entries.insert(key, after[..end].trim()).is_some() {
+            return Err(invalid(&format!("duplicate header key {}", key)));
+        }
+
+        rest = after[end..].trim_start();
+        rest = match rest.strip_prefix(',') {
+            Some(after) => after,
+            None if rest.starts_with('}') => rest,
+            None => return Err(invalid("header entries are not comma separated")),
+        };
+    }
+}
+
+/// Splits a leading single or double quoted string into its content and what follows it.
+fn split_quoted(text: &str) -> Option<(&str, &str)> {
+    let quote = text.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let end = text[1..].find(quote)? + 1;
+
+    Some((&text[1..end], &text[end + 1..]))
+}
+
+/// Parses a Python tuple of dimensions such as `()`, `(3,)` or `(3, 4)`.
+fn parse_shape(value: &str) -> Result<Vec<usize>, TensoError> {
+    value
+        .strip_prefix('(')
+        .and_then(|value| value.strip_suffix(')'))
+        .ok_or_else(|| invalid("shape is not a tuple"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.parse().map_err(|_| invalid("shape is not a tuple")))
+        .collect()
+}
@@ -0,0 +1,222 @@
+//! Minimal ZIP container, only stored (uncompressed) entries as written by `numpy.savez`.
+
+use std::io::Write;
+
+use crate::error::TensoError;
+
+use super::invalid;
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+
+const ZIP64_EXTRA: u16 = 0x0001;
+const ZIP64_MARKER: u32 = 0xffff_ffff;
+
+const STORED: u16 = 0;
+
+/// Name and content of every entry, in the order of the central directory.
+pub fn read_entries(archive: &[u8]) -> Result<Vec<(String, Vec<u8>)>, TensoError> {
+    // The end record is 22 bytes followed by a comment of at most 64KiB.
+    let search_start = archive.len().saturating_sub(22 + 0xffff);
+    let end = (search_start..archive.len().saturating_sub(21))
+        .rev()
+        .find(|offset| read_u32(archive, *offset) == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or_else(|| invalid("not a zip archive"))?;
+
+    let count = read_u16(archive, end + 10).ok_or_else(truncated)? as usize;
+    let mut offset = read_u32(archive, end + 16).ok_or_else(truncated)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if read_u32(archive, offset) != Some(CENTRAL_HEADER) {
+            return Err(invalid("corrupted zip central directory"));
+        }
+
+        let method = read_u16(archive, offset + 10).ok_or_else(truncated)?;
+        let compressed_size = read_u32(archive, offset + 20).ok_or_else(truncated)?;
+        let uncompressed_size = read_u32(archive, offset + 24).ok_or_else(truncated)?;
+        let name_len = read_u16(archive, offset + 28).ok_or_else(truncated)? as usize;
+        let extra_len = read_u16(archive, offset + 30).ok_or_else(truncated)? as usize;
+        let comment_len = read_u16(archive, offset + 32).ok_or_else(truncated)? as usize;
+        let header_offset = read_u32(archive, offset + 42).ok_or_else(truncated)?;
+
+        let name = archive
+            .get(offset + 46..offset + 46 + name_len)
+            .ok_or_else(truncated)?;
+        let name = String::from_utf8(name.to_vec()).map_err(|_| invalid("zip entry name"))?;
+
+        // Fields too large for 32 bits are marked and moved in order to the zip64 extra field.
+        let extra = archive
+            .get(offset + 46 + name_len..offset + 46 + name_len + extra_len)
+            .ok_or_else(truncated)?;
+        let mut zip64_fields = find_extra(extra, ZIP64_EXTRA)
+            .unwrap_or(&[])
+            .chunks_exact(8)
+            .map(|field| read_u64(field, 0));
+        let mut field = |value: u32| match value {
+            ZIP64_MARKER => zip64_fields.next().flatten().ok_or_else(truncated),
+            _ => Ok(value as u64),
+        };
+        field(uncompressed_size)?;
+        let size = field(compressed_size)? as usize;
+        let local_offset = field(header_offset)? as usize;
+
+        if method != STORED {
+            return Err(invalid("compressed zip entries are not supported"));
+        }
+
+        if read_u32(archive, local_offset) != Some(LOCAL_HEADER) {
+            return Err(invalid("corrupted zip entry"));
+        }
+        let local_name_len = read_u16(archive, local_offset + 26).ok_or_else(truncated)? as usize;
+        let local_extra_len = read_u16(archive, local_offset + 28).ok_or_else(truncated)? as usize;
+        let data_offset = local_offset + 30 + local_name_len + local_extra_len;
+        let data = data_offset
+            .checked_add(size)
+            .and_then(|data_end| archive.get(data_offset..data_end))
+            .ok_or_else(truncated)?;
+
+        entries.push((name, data.to_vec()));
+        offset += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(entries)
+}
+
+/// Writes the entries stored, without zip64 records so archives are limited to 4GiB.
+pub fn write_entries(
+    writer: &mut impl Write,
+    entries: &[(String, Vec<u8>)],
+) -> Result<(), TensoError> {
+    check_limits(entries)?;
+
+    let mut central_directory = Vec::new();
+    let mut offset = 0;
+    for (name, data) in entries {
+        let crc = crc32(data);
+
+        let mut local_header = Vec::with_capacity(30 + name.len());
+        local_header.extend(&LOCAL_HEADER.to_le_bytes());
+        entry_fields(&mut local_header, name, data, crc);
+        writer.write_all(&local_header)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(data)?;
+
+        central_directory.extend(&CENTRAL_HEADER.to_le_bytes());
+        central_directory.extend(&20u16.to_le_bytes()); // Version made by.
+        entry_fields(&mut central_directory, name, data, crc);
+        central_directory.extend(&[0; 6]); // Comment length, disk, internal attributes.
+        central_directory.extend(&0u32.to_le_bytes()); // External attributes.
+        central_directory.extend(&(offset as u32).to_le_bytes());
+        central_directory.extend(name.as_bytes());
+
+        offset += local_header.len() + name.len() + data.len();
+    }
+
+    let mut end = Vec::with_capacity(22);
+    end.extend(&END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+    end.extend(&[0; 4]); // Disk numbers.
+    end.extend(&(entries.len() as u16).to_le_bytes());
+    end.extend(&(entries.len() as u16).to_le_bytes());
+    end.extend(&(central_directory.len() as u32).to_le_bytes());
+    end.extend(&(offset as u32).to_le_bytes());
+    end.extend(&0u16.to_le_bytes()); // Comment length.
+
+    writer.write_all(&central_directory)?;
+    writer.write_all(&end)?;
+
+    Ok(())
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Checks up front that every count, size and offset fits its field, all ones being reserved for
+/// zip64 records.
+fn check_limits(entries: &[(String, Vec<u8>)]) -> Result<(), TensoError> {
+    if entries.len() >= 0xffff {
+        return Err(invalid("too many entries for a zip archive"));
+    }
+
+    let mut entries_len = 0;
+    let mut central_directory_len = 0;
+    for (name, data) in entries {
+        if name.len() > u16::MAX as usize {
+            return Err(invalid(&format!("zip entry name too long: {}", name)));
+        }
+        if data.len() >= ZIP64_MARKER as usize {
+            return Err(invalid(&format!("zip entry too large: {}", name)));
+        }
+        entries_len += 30 + name.len() + data.len();
+        central_directory_len += 46 + name.len();
+    }
+
+    if entries_len >= ZIP64_MARKER as usize || central_directory_len >= ZIP64_MARKER as usize {
+        return Err(invalid("zip archive larger than 4GiB"));
+    }
+
+    Ok(())
+}
+
+/// Fields shared by the local and central headers, from the version needed to the extra length.
+fn entry_fields(header: &mut Vec<u8>, name: &str, data: &[u8], crc: u32) {
+    header.extend(&20u16.to_le_bytes()); // Version needed.
+    header.extend(&0u16.to_le_bytes()); // Flags.
+    header.extend(&STORED.to_le_bytes());
+    header.extend(&0u16.to_le_bytes()); // Modification time.
+    header.extend(&0x21u16.to_le_bytes()); // Modification date, 1980-01-01.
+    header.extend(&crc.to_le_bytes());
+    header.extend(&(data.len() as u32).to_le_bytes());
+    header.extend(&(data.len() as u32).to_le_bytes());
+    header.extend(&(name.len() as u16).to_le_bytes());
+    header.extend(&0u16.to_le_bytes()); // Extra field length.
+}
+
+fn find_extra(mut extra: &[u8], id: u16) -> Option<&[u8]> {
+    while extra.len() >= 4 {
+        let len = read_u16(extra, 2)? as usize;
+        let data = extra.get(4..4 + len)?;
+        if read_u16(extra, 0)? == id {
+            return Some(data);
+        }
+        extra = &extra[4 + len..];
+    }
+
+    None
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn truncated() -> TensoError {
+    invalid("truncated zip archive")
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    let mut value = [0; 8];
+    value.copy_from_slice(bytes.get(offset..offset + 8)?);
+    Some(u64::from_le_bytes(value))
+}
@@ -0,0 +1,127 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{error::TensoError, matrix::Matrix};
+
+use super::Operation;
+
+/// First bytes of every checkpoint.
+const MAGIC: &[u8; 4] = b"TNSO";
+const VERSION: u32 = 1;
+
+// Layout, all numbers little endian:
+//
+//   magic      4 bytes
+//   version    u32
+//   count      u64
+//   count times:
+//     height   u64
+//     width    u64
+//     data     height * width f32, row-major
+
+impl Operation {
+    /// Writes the values of every variable reachable from this node to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), TensoError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_parameters(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Loads a checkpoint written by `save` from a graph with the same structure.
+    ///
+    /// Nothing is modified unless the parameter count and every shape match the graph.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<(), TensoError> {
+        self.read_parameters(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn write_parameters(&self, writer: &mut impl Write) -> Result<(), TensoError> {
+        let parameters = self.parameters();
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(parameters.len() as u64).to_le_bytes())?;
+        for parameter in parameters {
+            let value = parameter.read();
+            writer.write_all(&(value.height() as u64).to_le_bytes())?;
+            writer.write_all(&(value.width() as u64).to_le_bytes())?;
+
+            let bytes: Vec<u8> =
+                value.chain_data(|data_iter| data_iter.flat_map(|v| v.to_le_bytes()).collect());
+            writer.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_parameters(&self, reader: &mut impl Read) -> Result<(), TensoError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(TensoError::InvalidFormat {
+                reason: "not a checkpoint".to_string(),
+            });
+        }
+
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(TensoError::InvalidFormat {
+                reason: format!("unsupported checkpoint version {}", version),
+            });
+        }
+
+        let parameters = self.parameters();
+        let count = read_u64(reader)? as usize;
+        if count != parameters.len() {
+            return Err(TensoError::ParameterCountMismatch {
+                expected: parameters.len(),
+                found: count,
+            });
+        }
+
+        let mut values = Vec::with_capacity(count);
+        for parameter in parameters.iter() {
+            let height = read_u64(reader)? as usize;
+            let width = read_u64(reader)? as usize;
+
+            let (expected_height, expected_width) = parameter.read().shape();
+            if (expected_height, expected_width) != (height, width) {
+                return Err(TensoError::ShapeMismatch {
+                    op: "load",
+                    left: vec![expected_height, expected_width],
+                    right: vec![height, width],
+                });
+            }
+
+            let mut bytes = vec![0; height * width * 4];
+            reader.read_exact(&mut bytes)?;
+            let data = bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            values.push(Matrix::new(height, width, data));
+        }
+
+        for (parameter, value) in parameters.into_iter().zip(values) {
+            parameter.write().set(value);
+        }
+
+        Ok(())
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, TensoError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, TensoError> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
@@ -0,0 +1,27 @@
+use crate::{error::TensoError, tensor::Tensor};
+
+use super::{NaryOperation, Operation};
+
+/// Differentiable operation over any number of inputs, defined outside of the crate.
+///
+/// The graph takes care of ordering, caching and gradient accumulation, an implementation only
+/// maps input tensors to an output and an output gradient back to input gradients.
+///
+/// Graphs can be shared between threads, so an implementation must be `Send + Sync`.
+pub trait CustomOp: Send + Sync {
+    /// Validates the inputs before `forward`, which may assume valid shapes.
+    fn check(&self, _inputs: &[&Tensor]) -> Result<(), TensoError> {
+        Ok(())
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor;
+
+    /// Gradient of every input, in input order, given the gradient of the output.
+    fn backward(&self, inputs: &[&Tensor], output: &Tensor, grad: &Tensor) -> Vec<Tensor>;
+}
+
+impl Operation {
+    pub fn custom(inputs: &[Operation], op: impl CustomOp + 'static) -> Operation {
+        NaryOperation::new(inputs.to_vec(), op)
+    }
+}
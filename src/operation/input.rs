@@ -1,23 +1,40 @@
-use std::{cell::RefCell, ops::Deref, rc::Rc};
+use std::ops::Deref;
 
-use crate::{matrix::Matrix, optim::Optimizer};
+use crate::{
+    error::TensoError,
+    matrix::Matrix,
+    optim::{Optimizer, SharedMatrix},
+    tensor::Tensor,
+};
 
 use super::{Operation, OperationBase};
 
 /*------------------------------------------------------------------------------------------------*/
 
 pub struct InputPlaceholder {
-    value: Matrix,
+    name: Option<String>,
+    value: Tensor,
+    version: u64,
 }
 
 impl InputPlaceholder {
     pub fn new() -> Operation {
         Operation::new(Self {
-            value: Matrix::zeros(0, 0),
+            name: None,
+            value: Tensor::zeros(vec![0, 0]),
+            version: 0,
         })
     }
 
-    pub fn with_value(value: Matrix) -> Operation {
+    pub fn named(name: &str) -> Operation {
+        Operation::new(Self {
+            name: Some(name.to_string()),
+            value: Tensor::zeros(vec![0, 0]),
+            version: 0,
+        })
+    }
+
+    pub fn with_value(value: impl Into<Tensor>) -> Operation {
         let mut placeholder = Self::new();
         placeholder.set_input(value);
         placeholder
@@ -25,89 +42,188 @@ impl InputPlaceholder {
 }
 
 impl OperationBase for InputPlaceholder {
-    fn run(&mut self) -> Matrix {
-        self.get_output()
+    fn forward(&mut self) -> Result<(), TensoError> {
+        Ok(())
     }
 
-    fn back(&mut self) {}
+    fn back(&mut self) -> Result<(), TensoError> {
+        Ok(())
+    }
 
-    fn back_grad(&mut self, _: Matrix) {}
+    fn back_grad(&mut self, _: Tensor) {}
 
-    fn get_output(&self) -> Matrix {
+    fn propagate(&mut self) -> Result<(), TensoError> {
+        Ok(())
+    }
+
+    fn output(&self) -> &Tensor {
+        &self.value
+    }
+
+    fn get_output(&self) -> Tensor {
         self.value.clone()
     }
 
-    fn set_input(&mut self, input: Matrix) {
+    fn set_input(&mut self, input: Tensor) {
         self.value = input;
+        self.version += 1;
     }
 
     fn add_to_optimizer(&self, _: &mut dyn Optimizer) {}
+
+    fn parameters(&self) -> Vec<SharedMatrix> {
+        Vec::new()
+    }
+
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn children(&self) -> Vec<Operation> {
+        Vec::new()
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
 }
 
 /*------------------------------------------------------------------------------------------------*/
 
 struct Variable {
-    value: Rc<RefCell<Matrix>>,
-    grad: Rc<RefCell<Matrix>>,
+    name: Option<String>,
+    // Optimizers, state dicts and checkpoints work on the rank 2 view of the value.
+    value: SharedMatrix,
+    grad: SharedMatrix,
+    shape: Vec<usize>,
+
+    // Snapshot of `value` seen by the last forward pass, the optimizer updates `value` in place.
+    output: Tensor,
+    output_version: u64,
+    version: u64,
 }
 
 impl Variable {
-    fn new(value: Matrix) -> Operation {
-        let value = Rc::new(RefCell::new(value));
-        let grad = Rc::new(RefCell::new(Matrix::zeros(0, 0)));
+    fn new(name: Option<String>, value: Tensor) -> Operation {
+        let shape = value.shape().to_vec();
+        let output = value.clone();
+        let value = SharedMatrix::new(value.into());
+        let grad = SharedMatrix::new(Matrix::zeros(0, 0));
 
-        Operation::new(Self { value, grad })
+        Operation::new(Self {
+            name,
+            output_version: value.version(),
+            value,
+            grad,
+            shape,
+
+            output,
+            version: 0,
+        })
+    }
+
+    /// `value` with the shape of the variable, or as a matrix if it was resized from outside.
+    fn view(&self, value: &Matrix) -> Tensor {
+        let tensor = Tensor::from(value.clone());
+        if tensor.size() == self.shape.iter().product::<usize>() {
+            tensor.reshape(self.shape.clone())
+        } else {
+            tensor
+        }
     }
 }
 
 impl OperationBase for Variable {
-    fn run(&mut self) -> Matrix {
-        self.get_output()
+    fn forward(&mut self) -> Result<(), TensoError> {
+        let value = self.value.read();
+        let value_version = self.value.version();
+        if value_version != self.output_version {
+            self.output = self.view(&value);
+            self.output_version = value_version;
+            self.version += 1;
+        }
+
+        Ok(())
     }
 
-    fn back(&mut self) {
-        let grad = Matrix::from_const(
-            self.value.borrow().height(),
-            self.value.borrow().width(),
-            1.0,
-        );
+    fn back(&mut self) -> Result<(), TensoError> {
+        let grad = self.view(&self.value.read()).map(|_| 1.0);
         self.back_grad(grad);
+
+        Ok(())
     }
 
-    fn back_grad(&mut self, grad: Matrix) {
-        let mut grad_borrow = self.grad.borrow_mut();
-        let new_grad = if grad.width() == grad_borrow.width()
-            && grad.height() == grad_borrow.height()
-        {
-            Matrix::new(
-                grad.height(),
-                grad.width(),
-                grad.chain_zip_data(grad_borrow.deref(), |data_zip| {
-                    data_zip.map(|(v0, v1)| v0 + v1).collect()
-                }),
-            )
-        } else {
-            grad
-        };
+    fn back_grad(&mut self, grad: Tensor) {
+        let grad = Matrix::from(grad);
+        let mut grad_borrow = self.grad.write();
+        let new_grad =
+            if grad.width() == grad_borrow.width() && grad.height() == grad_borrow.height() {
+                grad.zip_map(grad_borrow.deref(), |v0, v1| v0 + v1)
+            } else {
+                grad
+            };
 
         grad_borrow.set(new_grad);
     }
 
-    fn get_output(&self) -> Matrix {
-        self.value.borrow().clone()
+    fn propagate(&mut self) -> Result<(), TensoError> {
+        Ok(())
+    }
+
+    fn output(&self) -> &Tensor {
+        &self.output
+    }
+
+    fn get_output(&self) -> Tensor {
+        self.view(&self.value.read())
     }
 
-    fn set_input(&mut self, input: Matrix) {
-        self.value.borrow_mut().set(input);
+    fn set_input(&mut self, input: Tensor) {
+        self.shape = input.shape().to_vec();
+        self.value.write().set(input.into());
     }
 
     fn add_to_optimizer(&self, optim: &mut dyn Optimizer) {
-        optim.add_variable(Rc::clone(&self.value), Rc::clone(&self.grad));
+        optim.add_variable(self.value.clone(), self.grad.clone());
+    }
+
+    fn parameters(&self) -> Vec<SharedMatrix> {
+        vec![self.value.clone()]
+    }
+
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn children(&self) -> Vec<Operation> {
+        Vec::new()
+    }
+
+    fn version(&self) -> u64 {
+        self.version
     }
 }
 
 impl Matrix {
     pub fn as_variable(self) -> Operation {
-        Variable::new(self)
+        Variable::new(None, self.into())
+    }
+
+    /// Variable with a name, the key of its value in `Operation::state_dict`.
+    pub fn as_named_variable(self, name: &str) -> Operation {
+        Variable::new(Some(name.to_string()), self.into())
+    }
+}
+
+impl Tensor {
+    /// Variable keeping the shape of the tensor in the graph, its value is stored and optimized
+    /// as the rank 2 view.
+    pub fn as_variable(self) -> Operation {
+        Variable::new(None, self)
+    }
+
+    /// Variable with a name, the key of the rank 2 view of its value in `Operation::state_dict`.
+    pub fn as_named_variable(self, name: &str) -> Operation {
+        Variable::new(Some(name.to_string()), self)
     }
 }
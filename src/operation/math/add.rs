@@ -1,27 +1,35 @@
 use std::ops::Add;
 
 use crate::{
-    matrix::Matrix,
+    error::TensoError,
     operation::{BinaryOperation, BinaryOperationRunner, Operation},
+    tensor::Tensor,
 };
 
 struct AddRunner;
 
 impl BinaryOperationRunner for AddRunner {
-    fn run(&self, input_left: &Matrix, input_right: &Matrix) -> Matrix {
-        debug_assert_eq!(input_left.width(), input_right.width());
-        debug_assert_eq!(input_left.height(), input_right.height());
-
-        let out_data = input_left.chain_zip_data(input_right, |zip| {
-            zip.map(|(v_left, v_right)| v_left + v_right).collect()
-        });
+    fn check(&self, input_left: &Tensor, input_right: &Tensor) -> Result<(), TensoError> {
+        match input_left.broadcast_shape(input_right) {
+            Some(_) => Ok(()),
+            None => Err(TensoError::ShapeMismatch {
+                op: "add",
+                left: input_left.shape().to_vec(),
+                right: input_right.shape().to_vec(),
+            }),
+        }
+    }
 
-        Matrix::new(input_left.height(), input_left.width(), out_data)
+    fn run(&self, input_left: &Tensor, input_right: &Tensor) -> Tensor {
+        input_left.broadcast_zip(input_right, |v_left, v_right| v_left + v_right)
     }
 
-    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Matrix) {
-        child_left.back_grad(grad.clone());
-        child_right.back_grad(grad.clone());
+    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Tensor) {
+        let shape_left = child_left.shape();
+        let shape_right = child_right.shape();
+
+        child_left.back_grad(grad.sum_to_shape(&shape_left));
+        child_right.back_grad(grad.sum_to_shape(&shape_right));
     }
 }
 
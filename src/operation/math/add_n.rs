@@ -0,0 +1,49 @@
+use crate::{
+    error::TensoError,
+    operation::{custom::CustomOp, NaryOperation, Operation},
+    tensor::Tensor,
+};
+
+/// Checks that there is at least one input and that all inputs have the same shape.
+pub(super) fn check_same_shape(op: &'static str, inputs: &[&Tensor]) -> Result<(), TensoError> {
+    let first = inputs.first().ok_or(TensoError::EmptyInput { op })?;
+    for input in inputs {
+        if input.shape() != first.shape() {
+            return Err(TensoError::ShapeMismatch {
+                op,
+                left: first.shape().to_vec(),
+                right: input.shape().to_vec(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+struct AddNRunner;
+
+impl CustomOp for AddNRunner {
+    fn check(&self, inputs: &[&Tensor]) -> Result<(), TensoError> {
+        check_same_shape("add_n", inputs)
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor {
+        let mut result = inputs[0].clone();
+        for input in &inputs[1..] {
+            result = result.zip_map(input, |v_sum, v| v_sum + v);
+        }
+
+        result
+    }
+
+    fn backward(&self, inputs: &[&Tensor], _: &Tensor, grad: &Tensor) -> Vec<Tensor> {
+        inputs.iter().map(|_| grad.clone()).collect()
+    }
+}
+
+impl Operation {
+    /// Elementwise sum of any number of inputs of the same shape.
+    pub fn add_n(inputs: &[Operation]) -> Operation {
+        NaryOperation::new(inputs.to_vec(), AddNRunner)
+    }
+}
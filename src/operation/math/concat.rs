@@ -0,0 +1,59 @@
+use crate::{
+    error::TensoError,
+    matrix::Axis,
+    operation::{custom::CustomOp, NaryOperation, Operation},
+    tensor::Tensor,
+};
+
+struct ConcatRunner {
+    axis: Axis,
+}
+
+impl CustomOp for ConcatRunner {
+    fn check(&self, inputs: &[&Tensor]) -> Result<(), TensoError> {
+        let first = inputs
+            .first()
+            .ok_or(TensoError::EmptyInput { op: "concat" })?;
+        let dim = first.axis_dim(self.axis);
+        for input in inputs {
+            let fits = input.axis_dim(self.axis) == dim
+                && input.reduced_shape(dim, true) == first.reduced_shape(dim, true);
+            if !fits {
+                return Err(TensoError::ShapeMismatch {
+                    op: "concat",
+                    left: first.shape().to_vec(),
+                    right: input.shape().to_vec(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor {
+        Tensor::concat(inputs, inputs[0].axis_dim(self.axis))
+    }
+
+    fn backward(&self, inputs: &[&Tensor], _: &Tensor, grad: &Tensor) -> Vec<Tensor> {
+        let dim = grad.axis_dim(self.axis);
+
+        let mut offset = 0;
+        inputs
+            .iter()
+            .map(|input| {
+                let len = input.dims()[dim];
+                let input_grad = grad.narrow(dim, offset, len);
+                offset += len;
+
+                input_grad.reshape(input.shape().to_vec())
+            })
+            .collect()
+    }
+}
+
+impl Operation {
+    /// Joins the outputs of `inputs` end to end along `axis` of their trailing matrices.
+    pub fn concat(inputs: &[Operation], axis: Axis) -> Operation {
+        NaryOperation::new(inputs.to_vec(), ConcatRunner { axis })
+    }
+}
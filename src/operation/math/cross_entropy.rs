@@ -0,0 +1,81 @@
+use crate::{
+    error::TensoError,
+    matrix::Axis,
+    operation::{BinaryOperation, BinaryOperationRunner, Operation},
+    tensor::Tensor,
+};
+
+use super::softmax::{log_softmax, softmax};
+
+/// Mean cross entropy between the softmax of the logits and the target distributions.
+///
+/// Classes run along the height of the trailing matrices, every lane along it is a sample, so a
+/// matrix holds one sample per column.
+struct CrossEntropyRunner;
+
+impl BinaryOperationRunner for CrossEntropyRunner {
+    fn check(&self, input_left: &Tensor, input_right: &Tensor) -> Result<(), TensoError> {
+        if input_left.shape() != input_right.shape() {
+            Err(TensoError::ShapeMismatch {
+                op: "cross_entropy",
+                left: input_left.shape().to_vec(),
+                right: input_right.shape().to_vec(),
+            })
+        } else if input_left.size() == 0 {
+            Err(TensoError::EmptyInput {
+                op: "cross_entropy",
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn run(&self, input_left: &Tensor, input_right: &Tensor) -> Tensor {
+        let dim = input_left.axis_dim(Axis::Height);
+        let log_probs = log_softmax(input_left, dim);
+        let total = log_probs.chain_zip_data(input_right, |zip| {
+            zip.map(|(log_prob, target)| -target * log_prob)
+                .sum::<f32>()
+        });
+
+        Tensor::scalar(total / input_left.lane_count(dim) as f32)
+    }
+
+    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Tensor) {
+        debug_assert_eq!(grad.size(), 1);
+
+        let logits = child_left.get_output_tensor();
+        let targets = child_right.get_output_tensor();
+        let dim = logits.axis_dim(Axis::Height);
+        let scale = grad.sum() / logits.lane_count(dim) as f32;
+
+        // d/dz of -sum(t * log_softmax(z)) is softmax(z) * sum(t) - t.
+        let probs = softmax(&logits, dim);
+        let mut grad_left = Tensor::zeros(logits.shape().to_vec());
+        for index in 0..logits.lane_count(dim) {
+            let lane_probs = probs.lane(dim, index);
+            let lane_targets = targets.lane(dim, index);
+
+            let target_sum: f32 = lane_targets.iter().sum();
+            let values: Vec<f32> = lane_probs
+                .iter()
+                .zip(&lane_targets)
+                .map(|(p, t)| scale * (p * target_sum - t))
+                .collect();
+            grad_left.set_lane(dim, index, &values);
+        }
+
+        let log_probs = log_softmax(&logits, dim);
+        let grad_right = log_probs.map(|v| -scale * v);
+
+        child_left.back_grad(grad_left);
+        child_right.back_grad(grad_right);
+    }
+}
+
+impl Operation {
+    /// Cross entropy of logits with one column per sample against targets of the same shape.
+    pub fn cross_entropy(self, targets: Operation) -> Self {
+        BinaryOperation::new(self, targets, CrossEntropyRunner)
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+struct ExpRunner;
+
+impl UnaryOperationRunner for ExpRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(f32::exp)
+    }
+
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        let child_grad = output.zip_map(grad, |out, gr| gr * out);
+
+        child.back_grad(child_grad);
+    }
+}
+
+impl Operation {
+    pub fn exp(self) -> Self {
+        UnaryOperation::new(self, ExpRunner)
+    }
+}
@@ -1,53 +1,38 @@
 use crate::{
-    matrix::Matrix,
+    error::TensoError,
     operation::{BinaryOperation, BinaryOperationRunner, Operation},
+    tensor::Tensor,
 };
 
 struct MatrixMultiplicationRunner;
 
 impl BinaryOperationRunner for MatrixMultiplicationRunner {
-    fn run(&self, input_left: &Matrix, input_right: &Matrix) -> Matrix {
-        debug_assert_eq!(input_left.width(), input_right.height());
-
-        let mut result = Matrix::zeros(input_left.height(), input_right.width());
-        for y in 0..input_left.height() {
-            for x in 0..input_right.width() {
-                let mut val: f32 = 0.0;
-                for i in 0..input_left.width() {
-                    val += input_left[y][i] * input_right[i][x];
-                }
-                result[y][x] = val;
-            }
+    fn check(&self, input_left: &Tensor, input_right: &Tensor) -> Result<(), TensoError> {
+        match input_left.matmul_shape(input_right) {
+            Some(_) => Ok(()),
+            None => Err(TensoError::ShapeMismatch {
+                op: "mmul",
+                left: input_left.shape().to_vec(),
+                right: input_right.shape().to_vec(),
+            }),
         }
+    }
 
-        result
+    fn run(&self, input_left: &Tensor, input_right: &Tensor) -> Tensor {
+        input_left.matmul(input_right)
     }
 
-    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Matrix) {
-        let input_left = child_left.get_output();
-        let input_right = child_right.get_output();
+    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Tensor) {
+        let input_left = child_left.get_output_tensor();
+        let input_right = child_right.get_output_tensor();
 
-        let mut grad_left = Matrix::zeros(input_left.height(), input_left.width());
-        for y in 0..grad_left.height() {
-            for x in 0..grad_left.width() {
-                let mut val: f32 = 0.0;
-                for i in 0..grad.width() {
-                    val += grad[y][i] * input_right[x][i];
-                }
-                grad_left[y][x] = val;
-            }
-        }
-
-        let mut grad_right = Matrix::zeros(input_right.height(), input_right.width());
-        for y in 0..grad_right.height() {
-            for x in 0..grad_right.width() {
-                let mut val: f32 = 0.0;
-                for i in 0..grad.height() {
-                    val += grad[i][x] * input_left[i][y];
-                }
-                grad_right[y][x] = val;
-            }
-        }
+        // Operands broadcast along the batch dimensions get the sum over the batch.
+        let grad_left = grad
+            .matmul_transposed(&input_right)
+            .sum_to_shape(input_left.shape());
+        let grad_right = input_left
+            .transposed_matmul(grad)
+            .sum_to_shape(input_right.shape());
 
         child_left.back_grad(grad_left);
         child_right.back_grad(grad_right);
@@ -55,6 +40,7 @@ impl BinaryOperationRunner for MatrixMultiplicationRunner {
 }
 
 impl Operation {
+    /// Matrix product of the trailing matrices, the leading batch dimensions broadcast.
     pub fn mmul(self, rhs: Operation) -> Self {
         BinaryOperation::new(self, rhs, MatrixMultiplicationRunner)
     }
@@ -0,0 +1,55 @@
+use crate::{
+    error::TensoError,
+    operation::{custom::CustomOp, NaryOperation, Operation},
+    tensor::Tensor,
+};
+
+use super::add_n::check_same_shape;
+
+struct MaxNRunner;
+
+impl CustomOp for MaxNRunner {
+    fn check(&self, inputs: &[&Tensor]) -> Result<(), TensoError> {
+        check_same_shape("max_n", inputs)
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor {
+        let mut result = inputs[0].clone();
+        for input in &inputs[1..] {
+            result = result.zip_map(input, f32::max);
+        }
+
+        result
+    }
+
+    /// The gradient of every element goes to the first input holding the maximum.
+    fn backward(&self, inputs: &[&Tensor], output: &Tensor, grad: &Tensor) -> Vec<Tensor> {
+        let values: Vec<Vec<f32>> = inputs
+            .iter()
+            .map(|input| input.chain_data(|data| data.copied().collect()))
+            .collect();
+
+        let mut grads = vec![vec![0.0; output.size()]; inputs.len()];
+        for (i, (out, gr)) in output
+            .chain_zip_data(grad, |zip| zip.map(|(o, g)| (*o, *g)).collect::<Vec<_>>())
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(index) = values.iter().position(|value| value[i] == out) {
+                grads[index][i] = gr;
+            }
+        }
+
+        grads
+            .into_iter()
+            .map(|data| Tensor::new(output.shape().to_vec(), data))
+            .collect()
+    }
+}
+
+impl Operation {
+    /// Elementwise maximum of any number of inputs of the same shape.
+    pub fn max_n(inputs: &[Operation]) -> Operation {
+        NaryOperation::new(inputs.to_vec(), MaxNRunner)
+    }
+}
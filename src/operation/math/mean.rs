@@ -1,43 +1,38 @@
-use std::slice::Iter;
-
 use crate::{
-    matrix::Matrix,
+    error::TensoError,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct MeanRunner;
 
 impl UnaryOperationRunner for MeanRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::from_const(
-            1,
-            1,
-            input.chain_data(|data_iter: Iter<f32>| data_iter.sum::<f32>())
-                / (input.width() * input.height()) as f32,
-        )
+    fn check(&self, input: &Tensor) -> Result<(), TensoError> {
+        if input.size() == 0 {
+            Err(TensoError::EmptyInput { op: "mean" })
+        } else {
+            Ok(())
+        }
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        debug_assert_eq!(grad.width(), 1);
-        debug_assert_eq!(grad.height(), 1);
+    fn run(&self, input: &Tensor) -> Tensor {
+        Tensor::scalar(input.sum() / input.size() as f32)
+    }
 
-        let child_in = child.get_output();
-        let mat_size = (child_in.width() * child_in.height()) as f32;
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        debug_assert_eq!(grad.size(), 1);
 
-        let grad_val = grad[0][0];
+        let shape = child.shape();
+        let size = shape.iter().product::<usize>() as f32;
 
-        let child_in = child.get_output();
-        let child_grad = Matrix::new(
-            child_in.height(),
-            child_in.width(),
-            child_in.chain_data(|child_data| child_data.map(|_| grad_val / mat_size).collect()),
-        );
+        let grad_val = grad.sum();
 
-        child.back_grad(child_grad);
+        child.back_grad(Tensor::from_const(shape, grad_val / size));
     }
 }
 
 impl Operation {
+    /// Mean of every element, as a rank 0 tensor.
     pub fn mean(self) -> Self {
         UnaryOperation::new(self, MeanRunner)
     }
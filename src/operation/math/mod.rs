@@ -1,10 +1,21 @@
 pub mod add;
+pub mod add_n;
+pub mod concat;
+pub mod cross_entropy;
+pub mod exp;
 pub mod matmul;
+pub mod max_n;
 pub mod mean;
 pub mod mul;
-pub mod times;
+pub mod pow;
+pub mod reduce;
 pub mod relu;
+pub mod reshape;
 pub mod sigmoid;
+pub mod softmax;
+pub mod stack;
 pub mod sub;
 pub mod sum;
-pub mod pow;
+pub mod tanh;
+pub mod times;
+pub mod transpose;
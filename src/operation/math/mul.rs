@@ -1,41 +1,41 @@
 use std::ops::Mul;
 
 use crate::{
-    matrix::Matrix,
+    error::TensoError,
     operation::{BinaryOperation, BinaryOperationRunner, Operation},
+    tensor::Tensor,
 };
 
 struct MulRunner;
 
 impl BinaryOperationRunner for MulRunner {
-    fn run(&self, input_left: &Matrix, input_right: &Matrix) -> Matrix {
-        debug_assert_eq!(input_left.width(), input_right.width());
-        debug_assert_eq!(input_left.height(), input_right.height());
-
-        Matrix::new(
-            input_left.height(),
-            input_left.width(),
-            input_left.chain_zip_data(input_right, |zip| {
-                zip.map(|(v_left, v_right)| v_left * v_right).collect()
+    fn check(&self, input_left: &Tensor, input_right: &Tensor) -> Result<(), TensoError> {
+        match input_left.broadcast_shape(input_right) {
+            Some(_) => Ok(()),
+            None => Err(TensoError::ShapeMismatch {
+                op: "mul",
+                left: input_left.shape().to_vec(),
+                right: input_right.shape().to_vec(),
             }),
-        )
+        }
     }
 
-    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Matrix) {
-        child_right.back_grad(Matrix::new(
-            grad.height(),
-            grad.width(),
-            child_left
-                .get_output()
-                .chain_zip_data(grad, |zip| zip.map(|(v_grad, v)| v_grad * v).collect()),
-        ));
-        child_left.back_grad(Matrix::new(
-            grad.height(),
-            grad.width(),
-            child_right
-                .get_output()
-                .chain_zip_data(grad, |zip| zip.map(|(v_grad, v)| v_grad * v).collect()),
-        ));
+    fn run(&self, input_left: &Tensor, input_right: &Tensor) -> Tensor {
+        input_left.broadcast_zip(input_right, |v_left, v_right| v_left * v_right)
+    }
+
+    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, grad: &Tensor) {
+        let input_left = child_left.get_output_tensor();
+        let input_right = child_right.get_output_tensor();
+
+        child_right.back_grad(
+            grad.broadcast_zip(&input_left, |v_grad, v| v_grad * v)
+                .sum_to_shape(input_right.shape()),
+        );
+        child_left.back_grad(
+            grad.broadcast_zip(&input_right, |v_grad, v| v_grad * v)
+                .sum_to_shape(input_left.shape()),
+        );
     }
 }
 
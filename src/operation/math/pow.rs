@@ -1,6 +1,6 @@
 use crate::{
-    matrix::Matrix,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct PowRunner {
@@ -8,25 +8,14 @@ struct PowRunner {
 }
 
 impl UnaryOperationRunner for PowRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::new(
-            input.height(),
-            input.width(),
-            input.chain_data(|data_iter| data_iter.map(|v| v.powf(self.power)).collect()),
-        )
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(|v| v.powf(self.power))
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        let child_in = child.get_output();
-        let child_grad = Matrix::new(
-            child_in.height(),
-            child_in.width(),
-            child_in.chain_zip_data(grad, |child_data| {
-                child_data
-                    .map(|(ci, gr)| gr * self.power * ci.powf(self.power - 1.0))
-                    .collect()
-            }),
-        );
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        let child_in = child.get_output_tensor();
+        let child_grad =
+            child_in.zip_map(grad, |ci, gr| gr * self.power * ci.powf(self.power - 1.0));
 
         child.back_grad(child_grad);
     }
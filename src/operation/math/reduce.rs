@@ -0,0 +1,219 @@
+use crate::{
+    error::TensoError,
+    matrix::Axis,
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+#[derive(Clone, Copy)]
+enum Reduction {
+    Sum,
+    Mean,
+    Max,
+    Min,
+}
+
+impl Reduction {
+    fn reduce(self, lane: &[f32]) -> f32 {
+        match self {
+            Reduction::Sum => lane.iter().sum(),
+            Reduction::Mean => lane.iter().sum::<f32>() / lane.len() as f32,
+            Reduction::Max => lane.iter().cloned().fold(f32::NEG_INFINITY, f32::max),
+            Reduction::Min => lane.iter().cloned().fold(f32::INFINITY, f32::min),
+        }
+    }
+
+    /// Gradient of a lane given the reduced value and its gradient.
+    fn lane_grad(self, lane: &[f32], reduced: f32, grad: f32) -> Vec<f32> {
+        match self {
+            Reduction::Sum => vec![grad; lane.len()],
+            Reduction::Mean => vec![grad / lane.len() as f32; lane.len()],
+            Reduction::Max | Reduction::Min => {
+                // Only the first extremum receives the gradient.
+                let mut lane_grad = vec![0.0; lane.len()];
+                if let Some(index) = lane.iter().position(|v| *v == reduced) {
+                    lane_grad[index] = grad;
+                }
+                lane_grad
+            }
+        }
+    }
+}
+
+/// Dimension to run along, resolved against the rank of the input at run time.
+#[derive(Clone, Copy)]
+enum Dim {
+    Axis(Axis),
+    Index(usize),
+}
+
+impl Dim {
+    fn check(self, op: &'static str, input: &Tensor) -> Result<(), TensoError> {
+        match self {
+            // Vectors are columns, so every input has at least the two dimensions of a matrix.
+            Dim::Index(dim) if dim >= input.rank().max(2) => Err(TensoError::DimOutOfRange {
+                op,
+                dim,
+                rank: input.rank(),
+            }),
+            _ if input.size() == 0 => Err(TensoError::EmptyInput { op }),
+            _ => Ok(()),
+        }
+    }
+
+    fn index(self, input: &Tensor) -> usize {
+        match self {
+            Dim::Axis(axis) => input.axis_dim(axis),
+            Dim::Index(dim) => dim,
+        }
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct ReduceRunner {
+    op: &'static str,
+    reduction: Reduction,
+    dim: Dim,
+    keep_dim: bool,
+}
+
+impl UnaryOperationRunner for ReduceRunner {
+    fn check(&self, input: &Tensor) -> Result<(), TensoError> {
+        self.dim.check(self.op, input)
+    }
+
+    fn run(&self, input: &Tensor) -> Tensor {
+        let dim = self.dim.index(input);
+        Tensor::new(
+            input.reduced_shape(dim, self.keep_dim),
+            (0..input.lane_count(dim))
+                .map(|index| self.reduction.reduce(&input.lane(dim, index)))
+                .collect(),
+        )
+    }
+
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        let input = child.get_output_tensor();
+        let dim = self.dim.index(&input);
+
+        let mut child_grad = Tensor::zeros(input.shape().to_vec());
+        for (index, (reduced, lane_grad)) in output
+            .chain_zip_data(grad, |zip| zip.map(|(o, g)| (*o, *g)).collect::<Vec<_>>())
+            .into_iter()
+            .enumerate()
+        {
+            let lane = input.lane(dim, index);
+            child_grad.set_lane(
+                dim,
+                index,
+                &self.reduction.lane_grad(&lane, reduced, lane_grad),
+            );
+        }
+
+        child.back_grad(child_grad);
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct ArgmaxRunner {
+    op: &'static str,
+    dim: Dim,
+}
+
+impl UnaryOperationRunner for ArgmaxRunner {
+    fn check(&self, input: &Tensor) -> Result<(), TensoError> {
+        self.dim.check(self.op, input)
+    }
+
+    fn run(&self, input: &Tensor) -> Tensor {
+        let dim = self.dim.index(input);
+        Tensor::new(
+            input.reduced_shape(dim, false),
+            input
+                .argmax(dim)
+                .into_iter()
+                .map(|index| index as f32)
+                .collect(),
+        )
+    }
+
+    /// Indices are piecewise constant, no gradient flows through them.
+    fn grad(&self, _: &mut Operation, _: &Tensor, _: &Tensor) {}
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+impl Operation {
+    /// Sums the lanes along `axis` of the trailing matrices.
+    ///
+    /// Keeping the dimension leaves it with a length of 1, dropping it removes it from the shape,
+    /// so a matrix becomes a column vector.
+    pub fn sum_axis(self, axis: Axis, keep_dim: bool) -> Self {
+        self.reduce("sum_axis", Reduction::Sum, Dim::Axis(axis), keep_dim)
+    }
+
+    pub fn mean_axis(self, axis: Axis, keep_dim: bool) -> Self {
+        self.reduce("mean_axis", Reduction::Mean, Dim::Axis(axis), keep_dim)
+    }
+
+    pub fn max_axis(self, axis: Axis, keep_dim: bool) -> Self {
+        self.reduce("max_axis", Reduction::Max, Dim::Axis(axis), keep_dim)
+    }
+
+    pub fn min_axis(self, axis: Axis, keep_dim: bool) -> Self {
+        self.reduce("min_axis", Reduction::Min, Dim::Axis(axis), keep_dim)
+    }
+
+    /// Index of the largest element of every lane along `axis`, with the axis dropped.
+    pub fn argmax(self, axis: Axis) -> Self {
+        UnaryOperation::new(
+            self,
+            ArgmaxRunner {
+                op: "argmax",
+                dim: Dim::Axis(axis),
+            },
+        )
+    }
+
+    /// Sums the lanes along any dimension, vectors count as `[len, 1]` columns.
+    pub fn sum_dim(self, dim: usize, keep_dim: bool) -> Self {
+        self.reduce("sum_dim", Reduction::Sum, Dim::Index(dim), keep_dim)
+    }
+
+    pub fn mean_dim(self, dim: usize, keep_dim: bool) -> Self {
+        self.reduce("mean_dim", Reduction::Mean, Dim::Index(dim), keep_dim)
+    }
+
+    pub fn max_dim(self, dim: usize, keep_dim: bool) -> Self {
+        self.reduce("max_dim", Reduction::Max, Dim::Index(dim), keep_dim)
+    }
+
+    pub fn min_dim(self, dim: usize, keep_dim: bool) -> Self {
+        self.reduce("min_dim", Reduction::Min, Dim::Index(dim), keep_dim)
+    }
+
+    /// Index of the largest element of every lane along `dim`, with the dimension dropped.
+    pub fn argmax_dim(self, dim: usize) -> Self {
+        UnaryOperation::new(
+            self,
+            ArgmaxRunner {
+                op: "argmax_dim",
+                dim: Dim::Index(dim),
+            },
+        )
+    }
+
+    fn reduce(self, op: &'static str, reduction: Reduction, dim: Dim, keep_dim: bool) -> Self {
+        UnaryOperation::new(
+            self,
+            ReduceRunner {
+                op,
+                reduction,
+                dim,
+                keep_dim,
+            },
+        )
+    }
+}
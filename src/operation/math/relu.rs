@@ -1,32 +1,18 @@
 use crate::{
-    matrix::Matrix,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct ReluRunner;
 
 impl UnaryOperationRunner for ReluRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::new(
-            input.height(),
-            input.width(),
-            input.chain_data(|data_iter| {
-                data_iter.map(|v| if *v > 0.0 { *v } else { 0.0 }).collect()
-            }),
-        )
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(|v| if v > 0.0 { v } else { 0.0 })
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        let child_in = child.get_output();
-        let child_grad = Matrix::new(
-            child_in.height(),
-            child_in.width(),
-            child_in.chain_zip_data(grad, |child_data| {
-                child_data
-                    .map(|(ci, gr)| if *ci > 0.0 { *gr } else { 0.0 })
-                    .collect()
-            }),
-        );
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        let child_in = child.get_output_tensor();
+        let child_grad = child_in.zip_map(grad, |ci, gr| if ci > 0.0 { gr } else { 0.0 });
 
         child.back_grad(child_grad);
     }
@@ -0,0 +1,63 @@
+use crate::{
+    error::TensoError,
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+struct ReshapeRunner {
+    shape: Vec<usize>,
+}
+
+impl UnaryOperationRunner for ReshapeRunner {
+    fn check(&self, input: &Tensor) -> Result<(), TensoError> {
+        if input.size() == self.shape.iter().product() {
+            Ok(())
+        } else {
+            Err(TensoError::ShapeMismatch {
+                op: "reshape",
+                left: input.shape().to_vec(),
+                right: self.shape.clone(),
+            })
+        }
+    }
+
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.clone().reshape(self.shape.clone())
+    }
+
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        child.back_grad(grad.clone().reshape(child.shape()));
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct FlattenRunner;
+
+impl UnaryOperationRunner for FlattenRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.clone().reshape(vec![input.size(), 1])
+    }
+
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        child.back_grad(grad.clone().reshape(child.shape()));
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+impl Operation {
+    pub fn reshape(self, height: usize, width: usize) -> Self {
+        self.reshape_to(vec![height, width])
+    }
+
+    /// Same row-major data viewed with any shape of the same size.
+    pub fn reshape_to(self, shape: Vec<usize>) -> Self {
+        UnaryOperation::new(self, ReshapeRunner { shape })
+    }
+
+    /// Reshapes the output into a column vector, whatever its shape at run time.
+    pub fn flatten(self) -> Self {
+        UnaryOperation::new(self, FlattenRunner)
+    }
+}
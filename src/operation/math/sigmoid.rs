@@ -1,6 +1,6 @@
 use crate::{
-    matrix::Matrix,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct SigmoidRunner;
@@ -9,33 +9,16 @@ impl SigmoidRunner {
     fn sigmoid(val: f32) -> f32 {
         1.0 / (1.0 + (-val).exp())
     }
-
-    fn sigmoid_prime(val: f32) -> f32 {
-        let sig = Self::sigmoid(val);
-        sig * (1.0 - sig)
-    }
 }
 
 impl UnaryOperationRunner for SigmoidRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::new(
-            input.height(),
-            input.width(),
-            input.chain_data(|data_iter| data_iter.map(|v| Self::sigmoid(*v)).collect()),
-        )
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(Self::sigmoid)
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        let child_in = child.get_output();
-        let child_grad = Matrix::new(
-            child_in.height(),
-            child_in.width(),
-            child_in.chain_zip_data(grad, |child_data| {
-                child_data
-                    .map(|(ci, gr)| gr * Self::sigmoid_prime(*ci))
-                    .collect()
-            }),
-        );
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        // sigmoid'(x) = sigmoid(x) * (1 - sigmoid(x)), reusing the forward output.
+        let child_grad = output.zip_map(grad, |out, gr| gr * out * (1.0 - out));
 
         child.back_grad(child_grad);
     }
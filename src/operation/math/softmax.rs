@@ -0,0 +1,104 @@
+use crate::{
+    matrix::Axis,
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+/// Numerically stable `log(softmax(x))` of every lane along `dim`.
+pub(super) fn log_softmax(input: &Tensor, dim: usize) -> Tensor {
+    let mut result = Tensor::zeros(input.shape().to_vec());
+    for index in 0..input.lane_count(dim) {
+        let lane = input.lane(dim, index);
+
+        let max = lane.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let log_sum = lane.iter().map(|v| (v - max).exp()).sum::<f32>().ln() + max;
+
+        let values: Vec<f32> = lane.iter().map(|v| v - log_sum).collect();
+        result.set_lane(dim, index, &values);
+    }
+
+    result
+}
+
+pub(super) fn softmax(input: &Tensor, dim: usize) -> Tensor {
+    let log_probs = log_softmax(input, dim);
+    log_probs.map(f32::exp)
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct SoftmaxRunner {
+    axis: Axis,
+}
+
+impl UnaryOperationRunner for SoftmaxRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        softmax(input, input.axis_dim(self.axis))
+    }
+
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        let dim = grad.axis_dim(self.axis);
+
+        let mut child_grad = Tensor::zeros(grad.shape().to_vec());
+        for index in 0..grad.lane_count(dim) {
+            let lane_probs = output.lane(dim, index);
+            let lane_grad = grad.lane(dim, index);
+
+            let dot: f32 = lane_probs.iter().zip(&lane_grad).map(|(p, g)| p * g).sum();
+            let values: Vec<f32> = lane_probs
+                .iter()
+                .zip(&lane_grad)
+                .map(|(p, g)| p * (g - dot))
+                .collect();
+            child_grad.set_lane(dim, index, &values);
+        }
+
+        child.back_grad(child_grad);
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct LogSoftmaxRunner {
+    axis: Axis,
+}
+
+impl UnaryOperationRunner for LogSoftmaxRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        log_softmax(input, input.axis_dim(self.axis))
+    }
+
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        let dim = grad.axis_dim(self.axis);
+        let probs = output.map(f32::exp);
+
+        let mut child_grad = Tensor::zeros(grad.shape().to_vec());
+        for index in 0..grad.lane_count(dim) {
+            let lane_probs = probs.lane(dim, index);
+            let lane_grad = grad.lane(dim, index);
+
+            let grad_sum: f32 = lane_grad.iter().sum();
+            let values: Vec<f32> = lane_probs
+                .iter()
+                .zip(&lane_grad)
+                .map(|(p, g)| g - p * grad_sum)
+                .collect();
+            child_grad.set_lane(dim, index, &values);
+        }
+
+        child.back_grad(child_grad);
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+impl Operation {
+    /// Softmax of every lane along `axis` of the trailing matrices.
+    pub fn softmax(self, axis: Axis) -> Self {
+        UnaryOperation::new(self, SoftmaxRunner { axis })
+    }
+
+    pub fn log_softmax(self, axis: Axis) -> Self {
+        UnaryOperation::new(self, LogSoftmaxRunner { axis })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    error::TensoError,
+    operation::{custom::CustomOp, NaryOperation, Operation},
+    tensor::Tensor,
+};
+
+struct StackRunner;
+
+impl CustomOp for StackRunner {
+    fn check(&self, inputs: &[&Tensor]) -> Result<(), TensoError> {
+        let first = inputs
+            .first()
+            .ok_or(TensoError::EmptyInput { op: "stack" })?;
+        for input in inputs {
+            if input.size() != first.size() {
+                return Err(TensoError::ShapeMismatch {
+                    op: "stack",
+                    left: first.shape().to_vec(),
+                    right: input.shape().to_vec(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor {
+        let mut result = Tensor::zeros(vec![inputs[0].size(), inputs.len()]);
+        for (x, input) in inputs.iter().enumerate() {
+            let column: Vec<f32> = input.chain_data(|data| data.copied().collect());
+            result.set_lane(0, x, &column);
+        }
+
+        result
+    }
+
+    fn backward(&self, inputs: &[&Tensor], _: &Tensor, grad: &Tensor) -> Vec<Tensor> {
+        inputs
+            .iter()
+            .enumerate()
+            .map(|(x, input)| Tensor::new(input.shape().to_vec(), grad.lane(0, x)))
+            .collect()
+    }
+}
+
+impl Operation {
+    /// Flattens every input into a column and places the columns side by side, turning
+    /// per-sample column vectors into a batch.
+    pub fn stack(inputs: &[Operation]) -> Operation {
+        NaryOperation::new(inputs.to_vec(), StackRunner)
+    }
+}
@@ -1,33 +1,26 @@
 use crate::{
-    matrix::Matrix,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct SumRunner;
 
 impl UnaryOperationRunner for SumRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::from_const(1, 1, input.chain_data(|data_iter| data_iter.sum::<f32>()))
+    fn run(&self, input: &Tensor) -> Tensor {
+        Tensor::scalar(input.sum())
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        debug_assert_eq!(grad.width(), 1);
-        debug_assert_eq!(grad.height(), 1);
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        debug_assert_eq!(grad.size(), 1);
 
-        let grad_val = grad[0][0];
+        let grad_val = grad.sum();
 
-        let child_in = child.get_output();
-        let child_grad = Matrix::new(
-            child_in.height(),
-            child_in.width(),
-            child_in.chain_data(|child_data| child_data.map(|_| grad_val).collect()),
-        );
-
-        child.back_grad(child_grad);
+        child.back_grad(Tensor::from_const(child.shape(), grad_val));
     }
 }
 
 impl Operation {
+    /// Sum of every element, as a rank 0 tensor.
     pub fn sum(self) -> Self {
         UnaryOperation::new(self, SumRunner)
     }
@@ -0,0 +1,25 @@
+use crate::{
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+struct TanhRunner;
+
+impl UnaryOperationRunner for TanhRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(f32::tanh)
+    }
+
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor) {
+        // tanh'(x) = 1 - tanh(x)^2
+        let child_grad = output.zip_map(grad, |out, gr| gr * (1.0 - out * out));
+
+        child.back_grad(child_grad);
+    }
+}
+
+impl Operation {
+    pub fn tanh(self) -> Self {
+        UnaryOperation::new(self, TanhRunner)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    matrix::Matrix,
     operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
 };
 
 struct TimesRunner {
@@ -8,20 +8,12 @@ struct TimesRunner {
 }
 
 impl UnaryOperationRunner for TimesRunner {
-    fn run(&self, input: &Matrix) -> Matrix {
-        Matrix::new(
-            input.height(),
-            input.width(),
-            input.chain_data(|data_iter| data_iter.map(|mat_val| self.value * mat_val).collect()),
-        )
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.map(|mat_val| self.value * mat_val)
     }
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix) {
-        child.back_grad(Matrix::new(
-            grad.height(),
-            grad.width(),
-            grad.chain_data(|di| di.map(|v| self.value * v).collect()),
-        ));
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        child.back_grad(grad.map(|v| self.value * v));
     }
 }
 
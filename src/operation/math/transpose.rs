@@ -0,0 +1,24 @@
+use crate::{
+    operation::{Operation, UnaryOperation, UnaryOperationRunner},
+    tensor::Tensor,
+};
+
+struct TransposeRunner;
+
+impl UnaryOperationRunner for TransposeRunner {
+    fn run(&self, input: &Tensor) -> Tensor {
+        input.transpose()
+    }
+
+    fn grad(&self, child: &mut Operation, _: &Tensor, grad: &Tensor) {
+        // A vector comes back from its transposed row as a column, hence the reshape.
+        child.back_grad(grad.transpose().reshape(child.shape()));
+    }
+}
+
+impl Operation {
+    /// Swaps the last two dimensions, transposing every trailing matrix.
+    pub fn transpose(self) -> Self {
+        UnaryOperation::new(self, TransposeRunner)
+    }
+}
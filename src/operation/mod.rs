@@ -1,84 +1,287 @@
-use crate::{matrix::Matrix, optim::Optimizer};
-use std::{cell::RefCell, rc::Rc};
-
+use crate::{
+    error::TensoError,
+    matrix::Matrix,
+    optim::{Optimizer, SharedMatrix},
+    tensor::Tensor,
+};
+use custom::CustomOp;
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+pub mod checkpoint;
+pub mod custom;
 pub mod input;
 pub mod math;
+pub mod state_dict;
 
 /*------------------------------------------------------------------------------------------------*/
 
+/// Handle to a node of the graph, clones share the node.
+///
+/// Nodes are behind `Arc<RwLock>` so a graph can be moved to another thread, and separate graphs
+/// built on the same variables can run from several threads at once.
 pub struct Operation {
-    op: Rc<RefCell<dyn OperationBase>>,
+    op: Arc<RwLock<dyn OperationBase>>,
 }
 
 impl Operation {
     fn new(op: impl OperationBase + 'static) -> Self {
         Self {
-            op: Rc::new(RefCell::new(op)),
+            op: Arc::new(RwLock::new(op)),
         }
     }
 
+    /// Evaluates every node reachable from this one exactly once, in topological order, and
+    /// returns the rank 2 view of the output, see `run_tensor` for the tensor itself.
+    ///
+    /// Nodes whose inputs did not change since the previous pass keep their cached output.
+    ///
+    /// # Panics
+    ///
+    /// When the shapes in the graph do not fit together, see `try_run`.
     pub fn run(&mut self) -> Matrix {
-        self.op.borrow_mut().run()
+        self.try_run().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_run(&mut self) -> Result<Matrix, TensoError> {
+        self.try_run_tensor().map(Matrix::from)
+    }
+
+    /// Same as `run`, keeping every dimension of the output.
+    pub fn run_tensor(&mut self) -> Tensor {
+        self.try_run_tensor()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_run_tensor(&mut self) -> Result<Tensor, TensoError> {
+        for op in self.topological_order() {
+            op.write().forward()?;
+        }
+
+        Ok(self.get_output_tensor())
     }
 
+    /// Backpropagates from this node in reverse topological order.
+    ///
+    /// Every interior node first accumulates the gradients of all its consumers and then
+    /// propagates the sum to its children once.
+    ///
+    /// # Panics
+    ///
+    /// When the output of this node holds more than one element, see `try_back`.
     pub fn back(&mut self) {
-        self.op.borrow_mut().back();
+        self.try_back().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_back(&mut self) -> Result<(), TensoError> {
+        self.write().back()?;
+
+        for op in self.topological_order().into_iter().rev() {
+            op.write().propagate()?;
+        }
+
+        Ok(())
     }
 
     pub fn get_output(&self) -> Matrix {
-        self.op.borrow().get_output()
+        self.read().get_output().into()
     }
 
-    pub fn set_input(&mut self, input: Matrix) {
-        self.op.borrow_mut().set_input(input);
+    pub fn get_output_tensor(&self) -> Tensor {
+        self.read().get_output()
     }
 
+    /// Replaces the value of a placeholder or variable, either a `Matrix` or a `Tensor`.
+    pub fn set_input(&mut self, input: impl Into<Tensor>) {
+        self.write().set_input(input.into());
+    }
+
+    /// Registers every variable reachable from this node once, in topological order.
     pub fn add_to_optimizer(&self, optim: &mut dyn Optimizer) {
-        self.op.borrow_mut().add_to_optimizer(optim);
+        for op in self.topological_order() {
+            op.read().add_to_optimizer(optim);
+        }
+    }
+
+    /// Values of every variable reachable from this node once, in topological order.
+    ///
+    /// The order only depends on the structure of the graph, so it is the same for every graph
+    /// built by the same code.
+    pub fn parameters(&self) -> Vec<SharedMatrix> {
+        self.topological_order()
+            .iter()
+            .flat_map(|op| op.read().parameters())
+            .collect()
+    }
+
+    /// Name given to a variable or placeholder, other nodes are anonymous.
+    pub fn name(&self) -> Option<String> {
+        self.read().name()
     }
 
     /*------------------------------------------------------*/
 
-    fn back_grad(&mut self, grad: Matrix) {
-        self.op.borrow_mut().back_grad(grad);
+    fn back_grad(&mut self, grad: Tensor) {
+        self.write().back_grad(grad);
+    }
+
+    fn version(&self) -> u64 {
+        self.read().version()
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.read().output().shape().to_vec()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, dyn OperationBase> {
+        self.op.read().expect("Operation lock poisoned!")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, dyn OperationBase + 'static> {
+        self.op.write().expect("Operation lock poisoned!")
+    }
+
+    fn id(&self) -> *const () {
+        Arc::as_ptr(&self.op) as *const ()
+    }
+
+    /// Every node reachable from this one, children before their parents.
+    fn topological_order(&self) -> Vec<Operation> {
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+
+        let mut stack = vec![(self.clone(), false)];
+        while let Some((op, expanded)) = stack.pop() {
+            if expanded {
+                order.push(op);
+                continue;
+            }
+
+            if !visited.insert(op.id()) {
+                continue;
+            }
+
+            let children = op.read().children();
+            stack.push((op, true));
+            for child in children.into_iter().rev() {
+                if !visited.contains(&child.id()) {
+                    stack.push((child, false));
+                }
+            }
+        }
+
+        order
     }
 }
 
 impl Clone for Operation {
     fn clone(&self) -> Self {
         Operation {
-            op: Rc::clone(&self.op),
+            op: Arc::clone(&self.op),
         }
     }
 }
 
 /*------------------------------------------------------------------------------------------------*/
 
-trait OperationBase {
-    fn run(&mut self) -> Matrix;
+trait OperationBase: Send + Sync {
+    /// Recomputes the output from the children outputs if any of them changed.
+    fn forward(&mut self) -> Result<(), TensoError>;
+
+    /// Seeds the gradient of the node backward starts from.
+    fn back(&mut self) -> Result<(), TensoError>;
 
-    fn back(&mut self);
+    /// Accumulates a gradient coming from one of the consumers of this node.
+    fn back_grad(&mut self, grad: Tensor);
 
-    fn back_grad(&mut self, grad: Matrix);
+    /// Sends the accumulated gradient to the children.
+    fn propagate(&mut self) -> Result<(), TensoError>;
 
-    fn get_output(&self) -> Matrix;
+    fn output(&self) -> &Tensor;
 
-    fn set_input(&mut self, input: Matrix);
+    fn get_output(&self) -> Tensor;
 
+    fn set_input(&mut self, input: Tensor);
+
+    /// Registers the variables held by this node itself, not the ones of its children.
     fn add_to_optimizer(&self, optim: &mut dyn Optimizer);
+
+    /// Values of the variables held by this node itself.
+    fn parameters(&self) -> Vec<SharedMatrix>;
+
+    fn name(&self) -> Option<String>;
+
+    fn children(&self) -> Vec<Operation>;
+
+    /// Incremented every time the output changes.
+    fn version(&self) -> u64;
+}
+
+/// Gradient backward starts from, a one for the single element of `output`.
+fn scalar_seed(output: &Tensor) -> Result<Tensor, TensoError> {
+    if output.size() == 1 {
+        Ok(Tensor::from_const(output.shape().to_vec(), 1.0))
+    } else {
+        Err(TensoError::NonScalarBackward {
+            shape: output.shape().to_vec(),
+        })
+    }
+}
+
+/// Read guards of the distinct nodes in `ops` and, for every op, the index of its guard.
+///
+/// An op used as several inputs is locked once, a thread can't reliably read lock twice.
+fn read_distinct<'a>(
+    ops: impl IntoIterator<Item = &'a Operation>,
+) -> (Vec<RwLockReadGuard<'a, dyn OperationBase>>, Vec<usize>) {
+    let mut ids = Vec::new();
+    let mut guards = Vec::new();
+    let mut indices = Vec::new();
+    for op in ops {
+        match ids.iter().position(|id| *id == op.id()) {
+            Some(index) => indices.push(index),
+            None => {
+                ids.push(op.id());
+                indices.push(guards.len());
+                guards.push(op.read());
+            }
+        }
+    }
+
+    (guards, indices)
+}
+
+fn accumulate_grad(acc: &mut Option<Tensor>, grad: Tensor) {
+    *acc = Some(match acc.take() {
+        Some(current) => grad.zip_map(&current, |v0, v1| v0 + v1),
+        None => grad,
+    });
 }
 
 /*------------------------------------------------------------------------------------------------*/
 
-trait UnaryOperationRunner {
-    fn run(&self, input: &Matrix) -> Matrix;
+trait UnaryOperationRunner: Send + Sync {
+    /// Validates the input before `run`, which may assume a valid shape.
+    fn check(&self, _input: &Tensor) -> Result<(), TensoError> {
+        Ok(())
+    }
+
+    fn run(&self, input: &Tensor) -> Tensor;
 
-    fn grad(&self, child: &mut Operation, grad: &Matrix);
+    /// `output` is the result of the last `run`, so activations can reuse it instead of
+    /// recomputing their forward pass.
+    fn grad(&self, child: &mut Operation, output: &Tensor, grad: &Tensor);
 }
 
 struct UnaryOperation<R: UnaryOperationRunner + 'static> {
     op_input: Operation,
-    output: Matrix,
+    output: Tensor,
+    grad: Option<Tensor>,
+
+    input_versions: Vec<u64>,
+    version: u64,
 
     runner: R,
 }
@@ -87,58 +290,105 @@ impl<R: UnaryOperationRunner + 'static> UnaryOperation<R> {
     fn new(op_input: Operation, runner: R) -> Operation {
         Operation::new(Self {
             op_input,
-            output: Matrix::zeros(0, 0),
+            output: Tensor::zeros(vec![0, 0]),
+            grad: None,
+
+            input_versions: Vec::new(),
+            version: 0,
+
             runner,
         })
     }
 }
 
 impl<R: UnaryOperationRunner> OperationBase for UnaryOperation<R> {
-    fn run(&mut self) -> Matrix {
-        let out_input = self.op_input.run();
-        self.output = self.runner.run(&out_input);
+    fn forward(&mut self) -> Result<(), TensoError> {
+        let input_versions = vec![self.op_input.version()];
+        if input_versions == self.input_versions {
+            return Ok(());
+        }
 
-        self.output.clone()
-    }
+        let input = self.op_input.read();
+        self.runner.check(input.output())?;
+        self.output = self.runner.run(input.output());
+        drop(input);
+
+        self.input_versions = input_versions;
+        self.version += 1;
 
-    fn back(&mut self) {
-        debug_assert!(
-            self.output.height() == 1 && self.output.width() == 1,
-            "Cant backpropagate a non-unit matrix!"
-        );
+        Ok(())
+    }
 
-        let grad = Matrix::from_const(1, 1, 1.0);
+    fn back(&mut self) -> Result<(), TensoError> {
+        let grad = scalar_seed(&self.output)?;
         self.back_grad(grad);
+
+        Ok(())
     }
 
-    fn back_grad(&mut self, grad: Matrix) {
-        self.runner.grad(&mut self.op_input, &grad);
+    fn back_grad(&mut self, grad: Tensor) {
+        accumulate_grad(&mut self.grad, grad);
     }
 
-    fn get_output(&self) -> Matrix {
+    fn propagate(&mut self) -> Result<(), TensoError> {
+        if let Some(grad) = self.grad.take() {
+            self.runner.grad(&mut self.op_input, &self.output, &grad);
+        }
+
+        Ok(())
+    }
+
+    fn output(&self) -> &Tensor {
+        &self.output
+    }
+
+    fn get_output(&self) -> Tensor {
         self.output.clone()
     }
 
-    fn set_input(&mut self, _: Matrix) {}
+    fn set_input(&mut self, _: Tensor) {}
+
+    fn add_to_optimizer(&self, _: &mut dyn Optimizer) {}
+
+    fn parameters(&self) -> Vec<SharedMatrix> {
+        Vec::new()
+    }
 
-    fn add_to_optimizer(&self, optim: &mut dyn Optimizer) {
-        self.op_input.add_to_optimizer(optim);
+    fn name(&self) -> Option<String> {
+        None
+    }
+
+    fn children(&self) -> Vec<Operation> {
+        vec![self.op_input.clone()]
+    }
+
+    fn version(&self) -> u64 {
+        self.version
     }
 }
 
 /*------------------------------------------------------------------------------------------------*/
 
-trait BinaryOperationRunner {
-    fn run(&self, input_left: &Matrix, input_right: &Matrix) -> Matrix;
+trait BinaryOperationRunner: Send + Sync {
+    /// Validates the inputs before `run`, which may assume valid shapes.
+    fn check(&self, _input_left: &Tensor, _input_right: &Tensor) -> Result<(), TensoError> {
+        Ok(())
+    }
 
-    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, gradient: &Matrix);
+    fn run(&self, input_left: &Tensor, input_right: &Tensor) -> Tensor;
+
+    fn grad(&self, child_left: &mut Operation, child_right: &mut Operation, gradient: &Tensor);
 }
 
 struct BinaryOperation<R: BinaryOperationRunner + 'static> {
     op_left: Operation,
     op_right: Operation,
 
-    output: Matrix,
+    output: Tensor,
+    grad: Option<Tensor>,
+
+    input_versions: Vec<u64>,
+    version: u64,
 
     runner: R,
 }
@@ -146,49 +396,215 @@ struct BinaryOperation<R: BinaryOperationRunner + 'static> {
 impl<R: BinaryOperationRunner + 'static> BinaryOperation<R> {
     fn new(op_left: Operation, op_right: Operation, runner: R) -> Operation {
         Operation::new(Self {
-            op_left: op_left,
-            op_right: op_right,
+            op_left,
+            op_right,
+
+            output: Tensor::zeros(vec![0, 0]),
+            grad: None,
+
+            input_versions: Vec::new(),
+            version: 0,
 
-            output: Matrix::zeros(0, 0),
             runner,
         })
     }
 }
 
 impl<R: BinaryOperationRunner + 'static> OperationBase for BinaryOperation<R> {
-    fn run(&mut self) -> Matrix {
-        let out_left = self.op_left.run();
-        let out_right = self.op_right.run();
+    fn forward(&mut self) -> Result<(), TensoError> {
+        let input_versions = vec![self.op_left.version(), self.op_right.version()];
+        if input_versions == self.input_versions {
+            return Ok(());
+        }
+
+        let (guards, indices) = read_distinct(vec![&self.op_left, &self.op_right]);
+        let input_left = guards[indices[0]].output();
+        let input_right = guards[indices[1]].output();
+        self.runner.check(input_left, input_right)?;
+        self.output = self.runner.run(input_left, input_right);
+        drop(guards);
+
+        self.input_versions = input_versions;
+        self.version += 1;
+
+        Ok(())
+    }
+
+    fn back(&mut self) -> Result<(), TensoError> {
+        let grad = scalar_seed(&self.output)?;
+        self.back_grad(grad);
+
+        Ok(())
+    }
 
-        self.output = self.runner.run(&out_left, &out_right);
+    fn back_grad(&mut self, grad: Tensor) {
+        accumulate_grad(&mut self.grad, grad);
+    }
+
+    fn propagate(&mut self) -> Result<(), TensoError> {
+        if let Some(grad) = self.grad.take() {
+            self.runner
+                .grad(&mut self.op_left, &mut self.op_right, &grad);
+        }
+
+        Ok(())
+    }
 
+    fn output(&self) -> &Tensor {
+        &self.output
+    }
+
+    fn get_output(&self) -> Tensor {
         self.output.clone()
     }
 
-    fn back(&mut self) {
-        debug_assert!(
-            self.output.height() == 1 && self.output.width() == 1,
-            "Cant backpropagate a non-unit matrix!"
-        );
+    fn set_input(&mut self, _: Tensor) {}
+
+    fn add_to_optimizer(&self, _: &mut dyn Optimizer) {}
+
+    fn parameters(&self) -> Vec<SharedMatrix> {
+        Vec::new()
+    }
+
+    fn name(&self) -> Option<String> {
+        None
+    }
+
+    fn children(&self) -> Vec<Operation> {
+        vec![self.op_left.clone(), self.op_right.clone()]
+    }
 
-        let grad = Matrix::from_const(1, 1, 1.0);
+    fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct NaryOperation<C: CustomOp + 'static> {
+    op_inputs: Vec<Operation>,
+
+    output: Tensor,
+    grad: Option<Tensor>,
+
+    /// `None` until the first run, so nodes without inputs are cached as well.
+    input_versions: Option<Vec<u64>>,
+    version: u64,
+
+    runner: C,
+}
+
+impl<C: CustomOp + 'static> NaryOperation<C> {
+    fn new(op_inputs: Vec<Operation>, runner: C) -> Operation {
+        Operation::new(Self {
+            op_inputs,
+
+            output: Tensor::zeros(vec![0, 0]),
+            grad: None,
+
+            input_versions: None,
+            version: 0,
+
+            runner,
+        })
+    }
+}
+
+impl<C: CustomOp + 'static> OperationBase for NaryOperation<C> {
+    fn forward(&mut self) -> Result<(), TensoError> {
+        let input_versions: Vec<u64> = self.op_inputs.iter().map(|op| op.version()).collect();
+        if self.input_versions.as_ref() == Some(&input_versions) {
+            return Ok(());
+        }
+
+        let (guards, indices) = read_distinct(&self.op_inputs);
+        let inputs: Vec<&Tensor> = indices.iter().map(|i| guards[*i].output()).collect();
+        self.runner.check(&inputs)?;
+        let output = self.runner.forward(&inputs);
+        drop(inputs);
+        drop(guards);
+
+        self.output = output;
+        self.input_versions = Some(input_versions);
+        self.version += 1;
+
+        Ok(())
+    }
+
+    fn back(&mut self) -> Result<(), TensoError> {
+        let grad = scalar_seed(&self.output)?;
         self.back_grad(grad);
+
+        Ok(())
     }
 
-    fn back_grad(&mut self, grad: Matrix) {
-        self.runner
-            .grad(&mut self.op_left, &mut self.op_right, &grad);
+    fn back_grad(&mut self, grad: Tensor) {
+        accumulate_grad(&mut self.grad, grad);
     }
 
-    fn get_output(&self) -> Matrix {
+    fn propagate(&mut self) -> Result<(), TensoError> {
+        if let Some(grad) = self.grad.take() {
+            let (guards, indices) = read_distinct(&self.op_inputs);
+            let inputs: Vec<&Tensor> = indices.iter().map(|i| guards[*i].output()).collect();
+            let grads = self.runner.backward(&inputs, &self.output, &grad);
+
+            // A misshapen gradient would replace the accumulated one instead of adding to it.
+            let op = std::any::type_name::<C>();
+            if grads.len() != inputs.len() {
+                return Err(TensoError::GradientCountMismatch {
+                    op,
+                    expected: inputs.len(),
+                    found: grads.len(),
+                });
+            }
+            if let Some((input, grad)) = inputs
+                .iter()
+                .zip(&grads)
+                .find(|(input, grad)| input.shape() != grad.shape())
+            {
+                return Err(TensoError::ShapeMismatch {
+                    op,
+                    left: input.shape().to_vec(),
+                    right: grad.shape().to_vec(),
+                });
+            }
+            drop(inputs);
+            drop(guards);
+
+            for (op_input, grad) in self.op_inputs.iter_mut().zip(grads) {
+                op_input.back_grad(grad);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn output(&self) -> &Tensor {
+        &self.output
+    }
+
+    fn get_output(&self) -> Tensor {
         self.output.clone()
     }
 
-    fn set_input(&mut self, _: Matrix) {}
+    fn set_input(&mut self, _: Tensor) {}
+
+    fn add_to_optimizer(&self, _: &mut dyn Optimizer) {}
+
+    fn parameters(&self) -> Vec<SharedMatrix> {
+        Vec::new()
+    }
+
+    fn name(&self) -> Option<String> {
+        None
+    }
+
+    fn children(&self) -> Vec<Operation> {
+        self.op_inputs.clone()
+    }
 
-    fn add_to_optimizer(&self, optim: &mut dyn Optimizer) {
-        self.op_left.add_to_optimizer(optim);
-        self.op_right.add_to_optimizer(optim);
+    fn version(&self) -> u64 {
+        self.version
     }
 }
 
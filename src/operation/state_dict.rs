@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use crate::{error::TensoError, matrix::Matrix, optim::SharedMatrix};
+
+use super::Operation;
+
+impl Operation {
+    /// Values of every named variable reachable from this node, by name.
+    ///
+    /// Anonymous variables are left out, two variables sharing a name are an error.
+    pub fn state_dict(&self) -> Result<BTreeMap<String, Matrix>, TensoError> {
+        Ok(self
+            .named_parameters()?
+            .into_iter()
+            .map(|(name, value)| {
+                let value = value.read().clone();
+                (name, value)
+            })
+            .collect())
+    }
+
+    /// Sets every named variable reachable from this node to its value in `state_dict`.
+    ///
+    /// Both sides need the same names and shapes, nothing is modified otherwise.
+    pub fn load_state_dict(&self, state_dict: &BTreeMap<String, Matrix>) -> Result<(), TensoError> {
+        let parameters = self.named_parameters()?;
+
+        for (name, value) in parameters.iter() {
+            let new_value = state_dict
+                .get(name)
+                .ok_or_else(|| TensoError::MissingKey { key: name.clone() })?;
+
+            let (height, width) = value.read().shape();
+            if (height, width) != new_value.shape() {
+                return Err(TensoError::ShapeMismatch {
+                    op: "load_state_dict",
+                    left: vec![height, width],
+                    right: vec![new_value.height(), new_value.width()],
+                });
+            }
+        }
+
+        if let Some(key) = state_dict
+            .keys()
+            .find(|key| !parameters.iter().any(|(name, _)| name == *key))
+        {
+            return Err(TensoError::UnexpectedKey { key: key.clone() });
+        }
+
+        for (name, value) in parameters {
+            value.write().set(state_dict[&name].clone());
+        }
+
+        Ok(())
+    }
+
+    /// Value of every named variable, placeholders hold no value and are skipped.
+    fn named_parameters(&self) -> Result<Vec<(String, SharedMatrix)>, TensoError> {
+        let mut parameters: Vec<(String, SharedMatrix)> = Vec::new();
+        for op in self.topological_order() {
+            let node = op.read();
+            if let (Some(name), [value]) = (node.name(), node.parameters().as_slice()) {
+                if parameters.iter().any(|(other, _)| *other == name) {
+                    return Err(TensoError::DuplicateKey { key: name });
+                }
+                parameters.push((name, value.clone()));
+            }
+        }
+
+        Ok(parameters)
+    }
+}
@@ -0,0 +1,153 @@
+use super::OptimizerRunner;
+use crate::matrix::Matrix;
+
+/// First and second moment estimates of a single variable, with the number of steps it took for
+/// the bias correction.
+struct Moments {
+    first: Matrix,
+    second: Matrix,
+    steps: i32,
+}
+
+pub struct AdamOptimizerRunner {
+    lr: f32,
+    beta1: f32,
+    beta2: f32,
+    epsilon: f32,
+
+    // Added to the gradient as an L2 penalty, or applied to the weights directly when decoupled.
+    weight_decay: f32,
+    decoupled_weight_decay: bool,
+
+    // One pair of moments per variable, matched by registration order. The optimizer only ever
+    // appends variables, those registered after the first step start from zeroed moments.
+    moments: Vec<Moments>,
+}
+
+impl AdamOptimizerRunner {
+    pub fn new(lr: f32) -> Self {
+        Self {
+            lr,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+
+            weight_decay: 0.0,
+            decoupled_weight_decay: false,
+
+            moments: Vec::new(),
+        }
+    }
+
+    pub fn with_betas(mut self, beta1: f32, beta2: f32) -> Self {
+        self.beta1 = beta1;
+        self.beta2 = beta2;
+        self
+    }
+
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// L2 penalty added to the gradient before the moments are updated.
+    pub fn with_weight_decay(mut self, weight_decay: f32) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
+}
+
+impl OptimizerRunner for AdamOptimizerRunner {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for (index, (value, grad)) in variables.into_iter().enumerate() {
+            if index == self.moments.len() {
+                self.moments.push(Moments {
+                    first: Matrix::zeros(value.height(), value.width()),
+                    second: Matrix::zeros(value.height(), value.width()),
+                    steps: 0,
+                });
+            }
+            let moments = &mut self.moments[index];
+            assert_eq!(
+                value.shape(),
+                moments.first.shape(),
+                "Adam variables changed since the first step!"
+            );
+
+            moments.steps += 1;
+            let bias_correction1 = 1.0 - self.beta1.powi(moments.steps);
+            let bias_correction2 = 1.0 - self.beta2.powi(moments.steps);
+
+            for y in 0..value.height() {
+                for x in 0..value.width() {
+                    let mut grad_value = grad[y][x];
+                    if self.decoupled_weight_decay {
+                        value[y][x] -= self.lr * self.weight_decay * value[y][x];
+                    } else {
+                        grad_value += self.weight_decay * value[y][x];
+                    }
+
+                    let first = self.beta1 * moments.first[y][x] + (1.0 - self.beta1) * grad_value;
+                    let second = self.beta2 * moments.second[y][x]
+                        + (1.0 - self.beta2) * grad_value * grad_value;
+                    moments.first[y][x] = first;
+                    moments.second[y][x] = second;
+
+                    let first_hat = first / bias_correction1;
+                    let second_hat = second / bias_correction2;
+                    value[y][x] -= self.lr * first_hat / (second_hat.sqrt() + self.epsilon);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f32) {
+        self.lr = lr;
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Adam with weight decay applied directly to the weights instead of through the gradient.
+pub struct AdamWOptimizerRunner {
+    adam: AdamOptimizerRunner,
+}
+
+impl AdamWOptimizerRunner {
+    pub fn new(lr: f32, weight_decay: f32) -> Self {
+        let mut adam = AdamOptimizerRunner::new(lr).with_weight_decay(weight_decay);
+        adam.decoupled_weight_decay = true;
+
+        Self { adam }
+    }
+
+    pub fn with_betas(self, beta1: f32, beta2: f32) -> Self {
+        Self {
+            adam: self.adam.with_betas(beta1, beta2),
+        }
+    }
+
+    pub fn with_epsilon(self, epsilon: f32) -> Self {
+        Self {
+            adam: self.adam.with_epsilon(epsilon),
+        }
+    }
+}
+
+impl OptimizerRunner for AdamWOptimizerRunner {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        self.adam.run(variables);
+    }
+
+    fn lr(&self) -> f32 {
+        self.adam.lr()
+    }
+
+    fn set_lr(&mut self, lr: f32) {
+        self.adam.set_lr(lr);
+    }
+}
@@ -1,26 +1,96 @@
 use std::{
-    cell::{RefCell, RefMut},
     ops::DerefMut,
-    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
 };
 
 use crate::matrix::Matrix;
 
+pub mod adam;
+pub mod scheduler;
 pub mod sgd;
 
+/// Matrix shared between a variable of the graph and the optimizer updating it.
+///
+/// Every write access bumps a version, so the graph notices in-place updates without comparing
+/// values.
+#[derive(Clone)]
+pub struct SharedMatrix {
+    value: Arc<RwLock<Matrix>>,
+    version: Arc<AtomicU64>,
+}
+
+impl SharedMatrix {
+    pub fn new(value: Matrix) -> Self {
+        Self {
+            value: Arc::new(RwLock::new(value)),
+            version: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, Matrix> {
+        self.value.read().expect("Variable lock poisoned!")
+    }
+
+    /// The version is bumped while the lock is held, a reader holding the read lock always sees
+    /// the version of the value it reads.
+    pub fn write(&self) -> RwLockWriteGuard<'_, Matrix> {
+        let guard = self.value.write().expect("Variable lock poisoned!");
+        self.version.fetch_add(1, Ordering::SeqCst);
+        guard
+    }
+
+    /// Number of write accesses so far.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Whether both share the same matrix.
+    pub fn ptr_eq(&self, other: &SharedMatrix) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
 pub trait Optimizer {
-    fn add_variable(&mut self, value: Rc<RefCell<Matrix>>, grad: Rc<RefCell<Matrix>>);
+    fn add_variable(&mut self, value: SharedMatrix, grad: SharedMatrix);
 
+    /// Updates the registered variables from their gradients, leaving the gradients untouched.
     fn step(&mut self);
+
+    /// Resets the gradients of the registered variables, gradients accumulate until then.
+    fn zero_grad(&mut self);
+
+    fn lr(&self) -> f32;
+
+    fn set_lr(&mut self, lr: f32);
 }
 
 pub trait OptimizerRunner {
-    fn run(&mut self, variables: Vec<(&mut Matrix, &mut Matrix)>);
+    /// Updates the value of every `(value, grad)` pair from its grad.
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>);
+
+    fn lr(&self) -> f32;
+
+    fn set_lr(&mut self, lr: f32);
+}
+
+/// Gradient clipping applied by `RunningOptimizer` before every step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradClip {
+    /// Clamps every gradient element into `[-value, value]`.
+    Value(f32),
+    /// Rescales all gradients together so their global L2 norm is at most the given one.
+    Norm(f32),
 }
 
 pub struct RunningOptimizer<O: OptimizerRunner + 'static> {
-    variables: Vec<(Rc<RefCell<Matrix>>, Rc<RefCell<Matrix>>)>,
+    variables: Vec<(SharedMatrix, SharedMatrix)>,
     runner: O,
+
+    grad_clip: Option<GradClip>,
+    last_grad_norm: Option<f32>,
 }
 
 impl<O: OptimizerRunner + 'static> RunningOptimizer<O> {
@@ -28,12 +98,51 @@ impl<O: OptimizerRunner + 'static> RunningOptimizer<O> {
         Self {
             variables: Vec::new(),
             runner,
+
+            grad_clip: None,
+            last_grad_norm: None,
         }
     }
+
+    pub fn with_grad_clip(mut self, grad_clip: GradClip) -> Self {
+        let bound = match grad_clip {
+            GradClip::Value(bound) | GradClip::Norm(bound) => bound,
+        };
+        assert!(
+            bound >= 0.0,
+            "Gradient clip bound must be non negative, got {}!",
+            bound
+        );
+
+        self.grad_clip = Some(grad_clip);
+        self
+    }
+
+    /// Global L2 norm of all the gradients before clipping, at the last step.
+    pub fn last_grad_norm(&self) -> Option<f32> {
+        self.last_grad_norm
+    }
+
+    /// Shapes of the registered variables, in registration order.
+    pub fn parameter_shapes(&self) -> Vec<(usize, usize)> {
+        self.variables
+            .iter()
+            .map(|(value, _)| value.read().shape())
+            .collect()
+    }
 }
 
 impl<O: OptimizerRunner + 'static> Optimizer for RunningOptimizer<O> {
-    fn add_variable(&mut self, value: Rc<RefCell<Matrix>>, grad: Rc<RefCell<Matrix>>) {
+    /// Variables are identified by their shared value, registering one again is a no-op.
+    fn add_variable(&mut self, value: SharedMatrix, grad: SharedMatrix) {
+        if self
+            .variables
+            .iter()
+            .any(|(registered, _)| registered.ptr_eq(&value))
+        {
+            return;
+        }
+
         self.variables.push((value, grad));
     }
 
@@ -41,14 +150,64 @@ impl<O: OptimizerRunner + 'static> Optimizer for RunningOptimizer<O> {
         let mut borrowed_variables = self
             .variables
             .iter()
-            .map(|(val, grad)| (val.borrow_mut(), grad.borrow_mut()))
-            .collect::<Vec<(RefMut<Matrix>, RefMut<Matrix>)>>();
+            .map(|(val, grad)| (val.write(), grad.read()))
+            .collect::<Vec<(RwLockWriteGuard<Matrix>, RwLockReadGuard<Matrix>)>>();
 
         let deref_variables = borrowed_variables
             .iter_mut()
-            .map(|(val, grad)| (val.deref_mut(), grad.deref_mut()))
-            .collect::<Vec<(&mut Matrix, &mut Matrix)>>();
+            .map(|(val, grad)| (val.deref_mut(), &**grad))
+            .collect::<Vec<(&mut Matrix, &Matrix)>>();
+
+        let grad_norm = deref_variables
+            .iter()
+            .map(|(_, grad)| grad.chain_data(|data_iter| data_iter.map(|v| v * v).sum::<f32>()))
+            .sum::<f32>()
+            .sqrt();
+        self.last_grad_norm = Some(grad_norm);
+
+        // Clipping works on scratch copies so the accumulated gradients stay untouched.
+        let clipped_grads: Option<Vec<Matrix>> = match self.grad_clip {
+            Some(GradClip::Value(max_value)) => Some(
+                deref_variables
+                    .iter()
+                    .map(|(_, grad)| grad.map(|v| v.max(-max_value).min(max_value)))
+                    .collect(),
+            ),
+            Some(GradClip::Norm(max_norm)) if grad_norm > max_norm => {
+                let scale = max_norm / grad_norm;
+                Some(
+                    deref_variables
+                        .iter()
+                        .map(|(_, grad)| grad.map(|v| v * scale))
+                        .collect(),
+                )
+            }
+            _ => None,
+        };
+
+        match &clipped_grads {
+            Some(clipped_grads) => self.runner.run(
+                deref_variables
+                    .into_iter()
+                    .zip(clipped_grads)
+                    .map(|((val, _), grad)| (val, grad))
+                    .collect(),
+            ),
+            None => self.runner.run(deref_variables),
+        }
+    }
+
+    fn zero_grad(&mut self) {
+        for (_, grad) in self.variables.iter() {
+            grad.write().clear();
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        self.runner.lr()
+    }
 
-        self.runner.run(deref_variables);
+    fn set_lr(&mut self, lr: f32) {
+        self.runner.set_lr(lr);
     }
 }
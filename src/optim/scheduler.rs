@@ -0,0 +1,229 @@
+use std::f32::consts::PI;
+
+use super::Optimizer;
+
+/// Drives the learning rate of an optimizer from one epoch to the next.
+///
+/// `step` is called at the start of every epoch, the first call captures the learning rate the
+/// optimizer was created with as the base of the schedule.
+pub trait LrScheduler {
+    /// Learning rate of the next epoch, `lr` is the one of the previous epoch.
+    fn next_lr(&mut self, lr: f32) -> f32;
+
+    fn step(&mut self, optim: &mut dyn Optimizer) {
+        let lr = self.next_lr(optim.lr());
+        optim.set_lr(lr);
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Multiplies the learning rate by `gamma` every `step_size` epochs.
+pub struct StepDecay {
+    step_size: usize,
+    gamma: f32,
+
+    base_lr: Option<f32>,
+    epoch: usize,
+}
+
+impl StepDecay {
+    pub fn new(step_size: usize, gamma: f32) -> Self {
+        assert!(step_size > 0, "StepDecay step size must be positive!");
+
+        Self {
+            step_size,
+            gamma,
+
+            base_lr: None,
+            epoch: 0,
+        }
+    }
+}
+
+impl LrScheduler for StepDecay {
+    fn next_lr(&mut self, lr: f32) -> f32 {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+        let epoch = self.epoch;
+        self.epoch += 1;
+
+        base_lr * self.gamma.powi((epoch / self.step_size) as i32)
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Multiplies the learning rate by `gamma` every epoch.
+pub struct ExponentialDecay {
+    gamma: f32,
+
+    base_lr: Option<f32>,
+    epoch: usize,
+}
+
+impl ExponentialDecay {
+    pub fn new(gamma: f32) -> Self {
+        Self {
+            gamma,
+
+            base_lr: None,
+            epoch: 0,
+        }
+    }
+}
+
+impl LrScheduler for ExponentialDecay {
+    fn next_lr(&mut self, lr: f32) -> f32 {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+        let epoch = self.epoch;
+        self.epoch += 1;
+
+        base_lr * self.gamma.powi(epoch as i32)
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Anneals the learning rate from the base down to `min_lr` along a half cosine, restarting
+/// after `period` epochs with a period `period_mult` times longer.
+pub struct CosineAnnealingWarmRestarts {
+    period_mult: usize,
+    min_lr: f32,
+
+    base_lr: Option<f32>,
+    current_period: usize,
+    epoch_in_period: usize,
+}
+
+impl CosineAnnealingWarmRestarts {
+    pub fn new(period: usize, period_mult: usize, min_lr: f32) -> Self {
+        assert!(
+            period > 0 && period_mult > 0,
+            "CosineAnnealingWarmRestarts period and period multiplier must be positive!"
+        );
+
+        Self {
+            period_mult,
+            min_lr,
+
+            base_lr: None,
+            current_period: period,
+            epoch_in_period: 0,
+        }
+    }
+}
+
+impl LrScheduler for CosineAnnealingWarmRestarts {
+    fn next_lr(&mut self, lr: f32) -> f32 {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+
+        if self.epoch_in_period == self.current_period {
+            self.epoch_in_period = 0;
+            self.current_period *= self.period_mult;
+        }
+        let progress = self.epoch_in_period as f32 / self.current_period as f32;
+        self.epoch_in_period += 1;
+
+        self.min_lr + (base_lr - self.min_lr) * (1.0 + (PI * progress).cos()) / 2.0
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Scales the learning rate linearly from `start_factor` times the base up to the base over
+/// `warmup_epochs` epochs, then keeps it constant.
+pub struct LinearWarmup {
+    warmup_epochs: usize,
+    start_factor: f32,
+
+    base_lr: Option<f32>,
+    epoch: usize,
+}
+
+impl LinearWarmup {
+    pub fn new(warmup_epochs: usize, start_factor: f32) -> Self {
+        Self {
+            warmup_epochs,
+            start_factor,
+
+            base_lr: None,
+            epoch: 0,
+        }
+    }
+}
+
+impl LrScheduler for LinearWarmup {
+    fn next_lr(&mut self, lr: f32) -> f32 {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+        let epoch = self.epoch;
+        self.epoch += 1;
+
+        if epoch >= self.warmup_epochs {
+            return base_lr;
+        }
+
+        let progress = epoch as f32 / self.warmup_epochs as f32;
+        base_lr * (self.start_factor + (1.0 - self.start_factor) * progress)
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Multiplies the learning rate by `factor` once the reported metric stopped decreasing for more
+/// than `patience` epochs.
+pub struct ReduceOnPlateau {
+    factor: f32,
+    patience: usize,
+    threshold: f32,
+    min_lr: f32,
+
+    best: Option<f32>,
+    bad_epochs: usize,
+}
+
+impl ReduceOnPlateau {
+    pub fn new(factor: f32, patience: usize) -> Self {
+        Self {
+            factor,
+            patience,
+            threshold: 1e-4,
+            min_lr: 0.0,
+
+            best: None,
+            bad_epochs: 0,
+        }
+    }
+
+    /// Relative decrease below the best metric needed to count as an improvement.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_min_lr(mut self, min_lr: f32) -> Self {
+        self.min_lr = min_lr;
+        self
+    }
+
+    /// Records the metric of the epoch that just ended, lower is better.
+    pub fn report(&mut self, metric: f32) {
+        match self.best {
+            Some(best) if metric >= best * (1.0 - self.threshold) => self.bad_epochs += 1,
+            _ => {
+                self.best = Some(metric);
+                self.bad_epochs = 0;
+            }
+        }
+    }
+}
+
+impl LrScheduler for ReduceOnPlateau {
+    fn next_lr(&mut self, lr: f32) -> f32 {
+        if self.bad_epochs <= self.patience {
+            return lr;
+        }
+
+        self.bad_epochs = 0;
+        (lr * self.factor).max(self.min_lr)
+    }
+}
@@ -3,28 +3,105 @@ use crate::matrix::Matrix;
 
 pub struct SGDOptimizerRunner {
     lr: f32,
+    momentum: f32,
+    dampening: f32,
+    nesterov: bool,
+    weight_decay: f32,
+
+    // One velocity buffer per variable, in registration order.
+    velocities: Vec<Matrix>,
 }
 
 impl SGDOptimizerRunner {
     pub fn new(lr: f32) -> Self {
-        Self { lr }
+        Self {
+            lr,
+            momentum: 0.0,
+            dampening: 0.0,
+            nesterov: false,
+            weight_decay: 0.0,
+
+            velocities: Vec::new(),
+        }
+    }
+
+    pub fn with_momentum(mut self, momentum: f32) -> Self {
+        self.momentum = momentum;
+        self
+    }
+
+    /// Scales down the contribution of the current gradient to the velocity by `1 - dampening`.
+    pub fn with_dampening(mut self, dampening: f32) -> Self {
+        self.dampening = dampening;
+        self
+    }
+
+    /// Steps along the velocity updated with the current gradient instead of the velocity alone.
+    pub fn with_nesterov(mut self, nesterov: bool) -> Self {
+        self.nesterov = nesterov;
+        self
+    }
+
+    /// L2 penalty added to the gradient.
+    pub fn with_weight_decay(mut self, weight_decay: f32) -> Self {
+        self.weight_decay = weight_decay;
+        self
     }
 }
 
 impl OptimizerRunner for SGDOptimizerRunner {
-    fn run(&mut self, variables: Vec<(&mut Matrix, &mut Matrix)>) {
-        for (value, grad) in variables {
-            for y in 0..value.height() {
-                for x in 0..value.width() {
-                    let current_value = value[y][x];
-                    let grad_value = grad[y][x];
-
-                    let new_value = current_value - self.lr * grad_value;
-                    value[y][x] = new_value;
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for (index, (value, grad)) in variables.into_iter().enumerate() {
+            if self.momentum == 0.0 {
+                for y in 0..value.height() {
+                    for x in 0..value.width() {
+                        let current_value = value[y][x];
+                        let grad_value = grad[y][x] + self.weight_decay * current_value;
+
+                        let new_value = current_value - self.lr * grad_value;
+                        value[y][x] = new_value;
+                    }
                 }
-            }
+            } else {
+                // The first step initializes the velocity with the undampened gradient.
+                let first_step = index == self.velocities.len();
+                if first_step {
+                    self.velocities
+                        .push(Matrix::zeros(value.height(), value.width()));
+                }
+                let velocity = &mut self.velocities[index];
+
+                for y in 0..value.height() {
+                    for x in 0..value.width() {
+                        let current_value = value[y][x];
+                        let grad_value = grad[y][x] + self.weight_decay * current_value;
 
-            grad.clear();
+                        let velocity_value = if first_step {
+                            grad_value
+                        } else {
+                            self.momentum * velocity[y][x] + (1.0 - self.dampening) * grad_value
+                        };
+                        velocity[y][x] = velocity_value;
+
+                        let step_value = if self.nesterov {
+                            grad_value + self.momentum * velocity_value
+                        } else {
+                            velocity_value
+                        };
+
+                        let new_value = current_value - self.lr * step_value;
+                        value[y][x] = new_value;
+                    }
+                }
+            }
         }
     }
+
+    fn lr(&self) -> f32 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f32) {
+        self.lr = lr;
+    }
 }
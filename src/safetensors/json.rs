@@ -0,0 +1,247 @@
+//! Just enough JSON for safetensors headers.
+
+use crate::error::TensoError;
+
+use super::invalid;
+
+pub enum Value {
+    /// `true`, `false` or `null`, headers never need to tell them apart.
+    Literal,
+    /// Kept as written so large integers don't lose precision.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Value::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Value, TensoError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+    };
+
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position != parser.chars.len() {
+        return Err(invalid("trailing characters after JSON header"));
+    }
+
+    Ok(value)
+}
+
+/// JSON string literal of `string`, quotes included.
+pub fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn value(&mut self) -> Result<Value, TensoError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('-') | Some('0'..='9') => Ok(Value::Number(self.number())),
+            Some('t') => self.keyword("true"),
+            Some('f') => self.keyword("false"),
+            Some('n') => self.keyword("null"),
+            _ => Err(invalid("malformed JSON header")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, TensoError> {
+        self.expect('{')?;
+
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((name, self.value()?));
+
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(members)),
+                _ => return Err(invalid("malformed JSON object")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, TensoError> {
+        self.expect('[')?;
+
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(invalid("malformed JSON array")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, TensoError> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+        loop {
+            match self.next_char() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.next_char() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(invalid("malformed JSON string")),
+                    };
+                    string.push(escaped);
+                }
+                Some(c) => string.push(c),
+                None => return Err(invalid("unterminated JSON string")),
+            }
+        }
+    }
+
+    /// The 4 hex digits after `\u`, and the low half that follows a high surrogate.
+    fn unicode_escape(&mut self) -> Result<char, TensoError> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if self.next_char() != Some('\\') || self.next_char() != Some('u') {
+                return Err(invalid("malformed JSON string"));
+            }
+            let low = self.hex4()?;
+            0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff)
+        } else {
+            high
+        };
+
+        std::char::from_u32(code).ok_or_else(|| invalid("malformed JSON string"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, TensoError> {
+        let digits: String = (0..4).filter_map(|_| self.next_char()).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| invalid("malformed JSON string"))
+    }
+
+    fn number(&mut self) -> String {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                break;
+            }
+            self.position += 1;
+        }
+
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<Value, TensoError> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+
+        Ok(Value::Literal)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TensoError> {
+        if self.next_char() == Some(expected) {
+            Ok(())
+        } else {
+            Err(invalid("malformed JSON header"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+}
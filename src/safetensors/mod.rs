@@ -0,0 +1,215 @@
+//! The safetensors format: a little-endian `u64` header length, a JSON header describing every
+//! tensor, then the raw little-endian data of all tensors.
+//!
+//! Tensors of rank 0 to 2 holding `F32`, `F64`, `F16` or `BF16` elements are read into `f32`
+//! matrices, rank 0 tensors become `1x1` matrices and rank 1 tensors column vectors. Matrices are
+//! always written as rank 2 `F32` tensors.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use crate::{error::TensoError, matrix::Matrix, operation::Operation};
+
+mod json;
+
+/// Header entry holding free form string metadata instead of a tensor.
+const METADATA_KEY: &str = "__metadata__";
+
+/// The header is padded with spaces so the data starts aligned to this.
+const ALIGNMENT: usize = 8;
+
+pub fn load_safetensors(path: impl AsRef<Path>) -> Result<BTreeMap<String, Matrix>, TensoError> {
+    read_safetensors(&fs::read(path)?)
+}
+
+pub fn save_safetensors(
+    path: impl AsRef<Path>,
+    matrices: &BTreeMap<String, Matrix>,
+) -> Result<(), TensoError> {
+    let mut writer = BufWriter::new(fs::File::create(path)?);
+    write_safetensors(&mut writer, matrices)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn read_safetensors(bytes: &[u8]) -> Result<BTreeMap<String, Matrix>, TensoError> {
+    let header_len = bytes
+        .get(..8)
+        .map(|len| {
+            let mut len_bytes = [0; 8];
+            len_bytes.copy_from_slice(len);
+            u64::from_le_bytes(len_bytes) as usize
+        })
+        .ok_or_else(|| invalid("truncated header"))?;
+    let header = bytes
+        .get(8..8usize.saturating_add(header_len))
+        .ok_or_else(|| invalid("truncated header"))?;
+    let header = std::str::from_utf8(header).map_err(|_| invalid("header is not text"))?;
+    let data = &bytes[8 + header_len..];
+
+    let tensors = match json::parse(header)? {
+        json::Value::Object(tensors) => tensors,
+        _ => return Err(invalid("header is not an object")),
+    };
+
+    let mut matrices = BTreeMap::new();
+    for (name, info) in tensors.iter() {
+        if name == METADATA_KEY {
+            continue;
+        }
+        if matrices
+            .insert(name.clone(), read_tensor(info, data)?)
+            .is_some()
+        {
+            return Err(invalid(&format!("duplicate tensor {}", name)));
+        }
+    }
+
+    Ok(matrices)
+}
+
+/// Writes the matrices sorted by name, each one as a rank 2 `F32` tensor.
+pub fn write_safetensors(
+    writer: &mut impl Write,
+    matrices: &BTreeMap<String, Matrix>,
+) -> Result<(), TensoError> {
+    let mut entries = Vec::with_capacity(matrices.len());
+    let mut offset = 0;
+    for (name, matrix) in matrices {
+        let size = matrix.height() * matrix.width() * 4;
+        entries.push(format!(
+            "{}:{{\"dtype\":\"F32\",\"shape\":[{},{}],\"data_offsets\":[{},{}]}}",
+            json::quote(name),
+            matrix.height(),
+            matrix.width(),
+            offset,
+            offset + size
+        ));
+        offset += size;
+    }
+
+    let mut header = format!("{{{}}}", entries.join(","));
+    let padding = (ALIGNMENT - header.len() % ALIGNMENT) % ALIGNMENT;
+    header.push_str(&" ".repeat(padding));
+
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for matrix in matrices.values() {
+        let bytes: Vec<u8> =
+            matrix.chain_data(|data_iter| data_iter.flat_map(|v| v.to_le_bytes()).collect());
+        writer.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+impl Operation {
+    /// Writes the state dict of the graph, the named variables by name.
+    pub fn save_safetensors(&self, path: impl AsRef<Path>) -> Result<(), TensoError> {
+        save_safetensors(path, &self.state_dict()?)
+    }
+
+    /// Loads the named variables of the graph from the tensors of the same name, see
+    /// `load_state_dict`.
+    pub fn load_safetensors(&self, path: impl AsRef<Path>) -> Result<(), TensoError> {
+        self.load_state_dict(&load_safetensors(path)?)
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+fn invalid(reason: &str) -> TensoError {
+    TensoError::InvalidFormat {
+        reason: reason.to_string(),
+    }
+}
+
+fn read_tensor(info: &json::Value, data: &[u8]) -> Result<Matrix, TensoError> {
+    let dtype = info
+        .get("dtype")
+        .and_then(json::Value::as_str)
+        .ok_or_else(|| invalid("tensor without dtype"))?;
+    let (element_size, convert): (usize, fn(&[u8]) -> f32) = match dtype {
+        "F32" => (4, |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        "F64" => (8, |b| {
+            f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+        }),
+        "F16" => (2, |b| f16_to_f32(u16::from_le_bytes([b[0], b[1]]))),
+        "BF16" => (2, |b| {
+            f32::from_bits((u16::from_le_bytes([b[0], b[1]]) as u32) << 16)
+        }),
+        _ => {
+            return Err(TensoError::UnsupportedDtype {
+                dtype: dtype.to_string(),
+            })
+        }
+    };
+
+    let shape = info
+        .get("shape")
+        .and_then(json::Value::as_array)
+        .and_then(|dims| {
+            dims.iter()
+                .map(json::Value::as_usize)
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| invalid("tensor without shape"))?;
+    let (height, width) = match shape.as_slice() {
+        [] => (1, 1),
+        [len] => (*len, 1),
+        [height, width] => (*height, *width),
+        _ => return Err(TensoError::UnsupportedRank { rank: shape.len() }),
+    };
+
+    let offsets = info
+        .get("data_offsets")
+        .and_then(json::Value::as_array)
+        .and_then(|offsets| match offsets {
+            [begin, end] => Some((begin.as_usize()?, end.as_usize()?)),
+            _ => None,
+        })
+        .ok_or_else(|| invalid("tensor without data_offsets"))?;
+    let bytes = data
+        .get(offsets.0..offsets.1)
+        .ok_or_else(|| invalid("data_offsets out of the data"))?;
+    let size = height
+        .checked_mul(width)
+        .and_then(|len| len.checked_mul(element_size))
+        .ok_or_else(|| invalid("tensor size overflows"))?;
+    if bytes.len() != size {
+        return Err(invalid("data_offsets do not match the shape"));
+    }
+
+    Ok(Matrix::new(
+        height,
+        width,
+        bytes.chunks_exact(element_size).map(convert).collect(),
+    ))
+}
+
+/// Widens an IEEE 754 half precision float.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    match exponent {
+        // Zero or subnormal, `mantissa * 2^-24`.
+        0 => {
+            let magnitude = mantissa as f32 * 2f32.powi(-24);
+            if sign == 0 {
+                magnitude
+            } else {
+                -magnitude
+            }
+        }
+        // Infinity or NaN.
+        0x1f => f32::from_bits(sign | (0xff << 23) | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
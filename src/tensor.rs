@@ -0,0 +1,570 @@
+use std::{
+    fmt::Display,
+    iter::Zip,
+    ops::{Index, IndexMut},
+    slice::Iter,
+};
+
+use rand::{distributions::Normal, Rng};
+
+use crate::{
+    gemm, kernel,
+    matrix::{Axis, Matrix},
+};
+
+/// N-dimensional row-major tensor, the value flowing through the graph.
+///
+/// A tensor of rank 2 or more is a batch of matrices stacked along its leading dimensions, the
+/// products, transposition and `Axis` based ops work on these trailing matrices. Rank 0 and 1
+/// tensors behave as the matrices they convert to, a `[1, 1]` scalar and a `[len, 1]` column,
+/// wherever an op looks at dimensions.
+///
+/// `Matrix` is the rank 2 view, converting folds all leading dimensions into the rows, so a
+/// `[batch, channels, width]` tensor becomes a `(batch * channels, width)` matrix.
+#[derive(Clone, PartialEq)]
+pub struct Tensor {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    data: Vec<f32>,
+}
+
+impl Tensor {
+    pub fn new(shape: Vec<usize>, data: Vec<f32>) -> Self {
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            data.len(),
+            "Tensor shape does not match the data size!"
+        );
+
+        Self {
+            strides: Self::row_major_strides(&shape),
+            shape,
+            data,
+        }
+    }
+
+    pub fn zeros(shape: Vec<usize>) -> Self {
+        Self::from_const(shape, 0.0)
+    }
+
+    /// Rank 0 tensor holding a single value.
+    pub fn scalar(value: f32) -> Self {
+        Self::new(Vec::new(), vec![value])
+    }
+
+    pub fn from_const(shape: Vec<usize>, value: f32) -> Self {
+        let size = shape.iter().product();
+        Self::new(shape, vec![value; size])
+    }
+
+    pub fn randn(shape: Vec<usize>, mean: f64, std: f64) -> Self {
+        let mut rng = rand::thread_rng();
+        let normal = Normal::new(mean, std);
+
+        let size = shape.iter().product();
+        Self::new(
+            shape,
+            (0..size).map(|_| rng.sample(normal) as f32).collect(),
+        )
+    }
+
+    /*------------------------------------------------------*/
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    pub fn rank(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Same data viewed with another shape of the same size.
+    pub fn reshape(self, shape: Vec<usize>) -> Self {
+        Self::new(shape, self.data)
+    }
+
+    /// Converts to a matrix, all dimensions but the last one are folded into the rows.
+    ///
+    /// Rank 0 tensors become `1x1` matrices and rank 1 tensors column vectors.
+    pub fn to_matrix(&self) -> Matrix {
+        let (height, width) = self.matrix_shape();
+        Matrix::new(height, width, self.data.clone())
+    }
+
+    pub fn chain_data<T>(&self, accessor: impl Fn(Iter<f32>) -> T) -> T {
+        accessor(self.data.iter())
+    }
+
+    pub fn chain_zip_data<T>(
+        &self,
+        other: &Tensor,
+        accessor: impl Fn(Zip<Iter<f32>, Iter<f32>>) -> T,
+    ) -> T {
+        accessor(self.data.iter().zip(other.data.iter()))
+    }
+
+    /*------------------------------------------------------*/
+
+    /// New tensor with `f` applied to every element.
+    pub fn map(&self, f: impl Fn(f32) -> f32 + Sync + Send) -> Tensor {
+        Tensor::new(self.shape.clone(), kernel::map(&self.data, f))
+    }
+
+    /// New tensor with `f` applied to the elements of both tensors pairwise, the shapes must match.
+    pub fn zip_map(&self, other: &Tensor, f: impl Fn(f32, f32) -> f32 + Sync + Send) -> Tensor {
+        assert_eq!(self.shape, other.shape, "Tensor shapes do not match!");
+
+        Tensor::new(
+            self.shape.clone(),
+            kernel::zip_map(&self.data, &other.data, f),
+        )
+    }
+
+    pub fn sum(&self) -> f32 {
+        kernel::sum(&self.data)
+    }
+
+    /// Shape both tensors broadcast to, NumPy style.
+    ///
+    /// Shapes are aligned on their last dimension and missing leading dimensions count as 1, each
+    /// dimension must then either be equal in both tensors or be 1 in one of them.
+    pub fn broadcast_shape(&self, other: &Tensor) -> Option<Vec<usize>> {
+        broadcast_shapes(&self.dims(), &other.dims())
+    }
+
+    /// Applies `f` elementwise after broadcasting both tensors to their common shape.
+    pub fn broadcast_zip(
+        &self,
+        other: &Tensor,
+        f: impl Fn(f32, f32) -> f32 + Sync + Send,
+    ) -> Tensor {
+        if self.shape == other.shape {
+            return self.zip_map(other, f);
+        }
+
+        let shape = self
+            .broadcast_shape(other)
+            .expect("Cant broadcast tensors of incompatible shapes!");
+        let strides_left = broadcast_strides(&self.dims(), &shape);
+        let strides_right = broadcast_strides(&other.dims(), &shape);
+
+        let mut data = Vec::with_capacity(shape.iter().product());
+        for_each_offset(&shape, &strides_left, &strides_right, |left, right| {
+            data.push(f(self.data[left], other.data[right]))
+        });
+
+        Tensor::new(shape, data)
+    }
+
+    /// Sums a broadcast tensor back down to `shape`, the reverse of broadcasting.
+    pub fn sum_to_shape(&self, shape: &[usize]) -> Tensor {
+        if self.shape == shape {
+            return self.clone();
+        }
+
+        let dims = self.dims();
+        let target = matrix_dims(shape);
+        assert!(
+            broadcast_shapes(&target, &dims).as_ref() == Some(&dims),
+            "Tensor can not be summed to that shape!"
+        );
+
+        let mut data = vec![0.0; target.iter().product()];
+        for_each_offset(
+            &dims,
+            &Self::row_major_strides(&dims),
+            &broadcast_strides(&target, &dims),
+            |offset, target_offset| data[target_offset] += self.data[offset],
+        );
+
+        Tensor::new(shape.to_vec(), data)
+    }
+
+    /*------------------------------------------------------*/
+
+    /// Shape of the product `self * rhs`, `None` when the tensors can not be multiplied.
+    ///
+    /// The trailing matrices are multiplied pairwise and the leading batch dimensions broadcast.
+    pub fn matmul_shape(&self, rhs: &Tensor) -> Option<Vec<usize>> {
+        let (dims_left, dims_right) = (self.dims(), rhs.dims());
+        let (batch_left, height, width) = split_matrix(&dims_left);
+        let (batch_right, rhs_height, rhs_width) = split_matrix(&dims_right);
+        if width != rhs_height {
+            return None;
+        }
+
+        let mut shape = broadcast_shapes(batch_left, batch_right)?;
+        shape.extend_from_slice(&[height, rhs_width]);
+        Some(shape)
+    }
+
+    /// Batched matrix product `self * rhs`.
+    pub fn matmul(&self, rhs: &Tensor) -> Tensor {
+        self.batched_product(rhs, Product::Plain)
+    }
+
+    /// Batched matrix product `self * rhs^T`, without building the transpose of `rhs`.
+    pub fn matmul_transposed(&self, rhs: &Tensor) -> Tensor {
+        self.batched_product(rhs, Product::RightTransposed)
+    }
+
+    /// Batched matrix product `self^T * rhs`, without building the transpose of `self`.
+    pub fn transposed_matmul(&self, rhs: &Tensor) -> Tensor {
+        self.batched_product(rhs, Product::LeftTransposed)
+    }
+
+    /// Swaps the last two dimensions, transposing every trailing matrix.
+    pub fn transpose(&self) -> Tensor {
+        let dims = self.dims();
+        let (batch, height, width) = split_matrix(&dims);
+
+        let size = height * width;
+        let mut data = vec![0.0; self.data.len()];
+        for offset in (0..batch.iter().product::<usize>()).map(|index| index * size) {
+            for y in 0..height {
+                for x in 0..width {
+                    data[offset + x * height + y] = self.data[offset + y * width + x];
+                }
+            }
+        }
+
+        let mut shape = batch.to_vec();
+        shape.extend_from_slice(&[width, height]);
+        Tensor::new(shape, data)
+    }
+
+    /*------------------------------------------------------*/
+
+    /// Dimension `axis` refers to, the rows or the columns of the trailing matrices.
+    pub fn axis_dim(&self, axis: Axis) -> usize {
+        let rank = self.rank().max(2);
+        match axis {
+            Axis::Height => rank - 2,
+            Axis::Width => rank - 1,
+        }
+    }
+
+    /// Number of lanes along `dim`, one for every position in the other dimensions.
+    pub fn lane_count(&self, dim: usize) -> usize {
+        let (outer, _, inner) = self.lane_layout(dim);
+        outer * inner
+    }
+
+    /// Elements of a lane along `dim`, lanes are numbered in row-major order of the other
+    /// dimensions.
+    pub fn lane(&self, dim: usize, index: usize) -> Vec<f32> {
+        let (_, len, inner) = self.lane_layout(dim);
+        let start = index / inner * len * inner + index % inner;
+        (0..len).map(|i| self.data[start + i * inner]).collect()
+    }
+
+    pub fn set_lane(&mut self, dim: usize, index: usize, values: &[f32]) {
+        let (_, len, inner) = self.lane_layout(dim);
+        let start = index / inner * len * inner + index % inner;
+        for (i, value) in values.iter().enumerate() {
+            self.data[start + i * inner] = *value;
+        }
+    }
+
+    /// Index of the largest element of every lane along `dim`, the first one on ties.
+    pub fn argmax(&self, dim: usize) -> Vec<usize> {
+        (0..self.lane_count(dim))
+            .map(|index| {
+                let lane = self.lane(dim, index);
+                (0..lane.len()).fold(0, |best, i| if lane[i] > lane[best] { i } else { best })
+            })
+            .collect()
+    }
+
+    /// Shape left by reducing along `dim`, which is kept with a length of 1 or dropped.
+    pub fn reduced_shape(&self, dim: usize, keep_dim: bool) -> Vec<usize> {
+        let mut shape = self.dims();
+        if keep_dim {
+            shape[dim] = 1;
+        } else {
+            shape.remove(dim);
+        }
+
+        shape
+    }
+
+    /// Joins tensors end to end along `dim`, every other dimension must match.
+    pub fn concat(tensors: &[&Tensor], dim: usize) -> Tensor {
+        let mut shape = tensors[0].dims();
+        shape[dim] = tensors.iter().map(|tensor| tensor.dims()[dim]).sum();
+        let outer: usize = shape[..dim].iter().product();
+
+        let mut data = Vec::with_capacity(shape.iter().product());
+        for index in 0..outer {
+            for tensor in tensors {
+                let (_, len, inner) = tensor.lane_layout(dim);
+                data.extend_from_slice(&tensor.data[index * len * inner..][..len * inner]);
+            }
+        }
+
+        Tensor::new(shape, data)
+    }
+
+    /// The `len` positions of `dim` starting at `start`.
+    pub fn narrow(&self, dim: usize, start: usize, len: usize) -> Tensor {
+        let (outer, dim_len, inner) = self.lane_layout(dim);
+        assert!(start + len <= dim_len, "Index out of bounds!");
+
+        let mut shape = self.dims();
+        shape[dim] = len;
+
+        let mut data = Vec::with_capacity(outer * len * inner);
+        for index in 0..outer {
+            data.extend_from_slice(&self.data[(index * dim_len + start) * inner..][..len * inner]);
+        }
+
+        Tensor::new(shape, data)
+    }
+
+    /// Shape with rank 0 and 1 tensors viewed as the matrices they convert to, `[1, 1]` and
+    /// `[len, 1]`.
+    pub fn dims(&self) -> Vec<usize> {
+        matrix_dims(&self.shape)
+    }
+
+    /*------------------------------------------------------*/
+
+    /// Product of the dimensions before `dim`, the length of `dim` and the product of the ones
+    /// after it, which is also the distance between two elements of a lane.
+    fn lane_layout(&self, dim: usize) -> (usize, usize, usize) {
+        let dims = self.dims();
+        assert!(dim < dims.len(), "Dimension out of range!");
+
+        (
+            dims[..dim].iter().product(),
+            dims[dim],
+            dims[dim + 1..].iter().product(),
+        )
+    }
+
+    fn batched_product(&self, rhs: &Tensor, product: Product) -> Tensor {
+        let (dims_left, dims_right) = (self.dims(), rhs.dims());
+        let (batch_left, height_left, width_left) = split_matrix(&dims_left);
+        let (batch_right, height_right, width_right) = split_matrix(&dims_right);
+
+        let (kernel, m, k, rhs_k, n): (Kernel, _, _, _, _) = match product {
+            Product::Plain => (
+                gemm::multiply,
+                height_left,
+                width_left,
+                height_right,
+                width_right,
+            ),
+            Product::RightTransposed => (
+                gemm::multiply_transposed,
+                height_left,
+                width_left,
+                width_right,
+                height_right,
+            ),
+            Product::LeftTransposed => (
+                gemm::transposed_multiply,
+                width_left,
+                height_left,
+                height_right,
+                width_right,
+            ),
+        };
+        assert_eq!(k, rhs_k, "Tensor shapes do not match!");
+
+        let batch = broadcast_shapes(batch_left, batch_right)
+            .expect("Cant broadcast tensors of incompatible shapes!");
+        let size_left = height_left * width_left;
+        let size_right = height_right * width_right;
+        let size = m * n;
+
+        let mut data = vec![0.0; batch.iter().product::<usize>() * size];
+        let mut offset = 0;
+        for_each_offset(
+            &batch,
+            &broadcast_strides(batch_left, &batch),
+            &broadcast_strides(batch_right, &batch),
+            |left, right| {
+                kernel(
+                    &self.data[left * size_left..][..size_left],
+                    &rhs.data[right * size_right..][..size_right],
+                    &mut data[offset..][..size],
+                    gemm::Dims { m, k, n },
+                );
+                offset += size;
+            },
+        );
+
+        let mut shape = batch;
+        shape.extend_from_slice(&[m, n]);
+        Tensor::new(shape, data)
+    }
+
+    fn matrix_shape(&self) -> (usize, usize) {
+        match self.shape.split_last() {
+            None => (1, 1),
+            Some((len, [])) => (*len, 1),
+            Some((width, leading)) => (leading.iter().product(), *width),
+        }
+    }
+
+    fn row_major_strides(shape: &[usize]) -> Vec<usize> {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+
+        strides
+    }
+
+    fn offset(&self, index: &[usize]) -> usize {
+        assert_eq!(
+            index.len(),
+            self.shape.len(),
+            "Index rank does not match the tensor!"
+        );
+
+        index
+            .iter()
+            .zip(self.shape.iter().zip(self.strides.iter()))
+            .map(|(i, (dim, stride))| {
+                assert!(i < dim, "Index out of bounds!");
+                i * stride
+            })
+            .sum()
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+/// Product of two row-major matrices written to the last slice, see `gemm`.
+type Kernel = fn(&[f32], &[f32], &mut [f32], gemm::Dims);
+
+#[derive(Clone, Copy)]
+enum Product {
+    Plain,
+    RightTransposed,
+    LeftTransposed,
+}
+
+fn matrix_dims(shape: &[usize]) -> Vec<usize> {
+    match shape {
+        [] => vec![1, 1],
+        [len] => vec![*len, 1],
+        _ => shape.to_vec(),
+    }
+}
+
+/// Batch dimensions, height and width of a shape of rank 2 or more.
+fn split_matrix(dims: &[usize]) -> (&[usize], usize, usize) {
+    let (batch, matrix) = dims.split_at(dims.len() - 2);
+    (batch, matrix[0], matrix[1])
+}
+
+fn broadcast_shapes(left: &[usize], right: &[usize]) -> Option<Vec<usize>> {
+    let rank = left.len().max(right.len());
+    let dim =
+        |shape: &[usize], i: usize| (i + shape.len()).checked_sub(rank).map_or(1, |i| shape[i]);
+
+    (0..rank)
+        .map(|i| {
+            let (dim_left, dim_right) = (dim(left, i), dim(right, i));
+            if dim_left == dim_right || dim_right == 1 {
+                Some(dim_left)
+            } else if dim_left == 1 {
+                Some(dim_right)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Strides to walk `shape` while iterating over the larger `target` it broadcasts to, broadcast
+/// dimensions get a stride of 0 so the same elements are visited again.
+fn broadcast_strides(shape: &[usize], target: &[usize]) -> Vec<usize> {
+    let strides = Tensor::row_major_strides(shape);
+    let missing = target.len() - shape.len();
+
+    (0..target.len())
+        .map(|i| match i.checked_sub(missing) {
+            Some(i) if shape[i] != 1 => strides[i],
+            _ => 0,
+        })
+        .collect()
+}
+
+/// Calls `f` with the offsets of both operands for every index of `shape`, in row-major order.
+fn for_each_offset(
+    shape: &[usize],
+    strides_left: &[usize],
+    strides_right: &[usize],
+    mut f: impl FnMut(usize, usize),
+) {
+    let mut index = vec![0; shape.len()];
+    let (mut left, mut right) = (0, 0);
+    for _ in 0..shape.iter().product::<usize>() {
+        f(left, right);
+
+        for dim in (0..shape.len()).rev() {
+            index[dim] += 1;
+            left += strides_left[dim];
+            right += strides_right[dim];
+            if index[dim] < shape[dim] {
+                break;
+            }
+
+            index[dim] = 0;
+            left -= strides_left[dim] * shape[dim];
+            right -= strides_right[dim] * shape[dim];
+        }
+    }
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+impl Index<&[usize]> for Tensor {
+    type Output = f32;
+
+    fn index(&self, index: &[usize]) -> &f32 {
+        &self.data[self.offset(index)]
+    }
+}
+
+impl IndexMut<&[usize]> for Tensor {
+    fn index_mut(&mut self, index: &[usize]) -> &mut f32 {
+        let offset = self.offset(index);
+        &mut self.data[offset]
+    }
+}
+
+impl From<Matrix> for Tensor {
+    fn from(matrix: Matrix) -> Self {
+        Self::new(
+            vec![matrix.height(), matrix.width()],
+            matrix.chain_data(|data_iter| data_iter.copied().collect()),
+        )
+    }
+}
+
+impl From<Tensor> for Matrix {
+    fn from(tensor: Tensor) -> Self {
+        let (height, width) = tensor.matrix_shape();
+        Matrix::new(height, width, tensor.data)
+    }
+}
+
+impl Display for Tensor {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(format!("Tensor {:?}\n", self.shape).as_str())?;
+        self.to_matrix().fmt(fmt)
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cell::RefCell, rc::Rc};
+
+use tenso_rs::{
+    self,
+    matrix::{Axis, Matrix},
+    operation::{input::InputPlaceholder, Operation},
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+struct GradOptimizer {
+    grads: Rc<RefCell<Vec<Matrix>>>,
+}
+
+impl OptimizerRunner for GradOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        *self.grads.borrow_mut() = variables
+            .into_iter()
+            .map(|(_, grad)| grad.clone())
+            .collect();
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+// Compares the analytic gradient of `sum(weights * f(x))` against central finite differences.
+fn check_grad(f: impl Fn(Operation) -> Operation) {
+    let input = Matrix::new(2, 3, vec![-2.0, -0.5, 0.0, 0.3, 1.0, 2.5]);
+    let weights =
+        InputPlaceholder::with_value(Matrix::new(2, 3, vec![0.5, -1.0, 2.0, 1.5, -0.3, 0.7]));
+
+    let var = input.clone().as_variable();
+    let mut loss_f = (weights.clone() * f(var.clone())).sum();
+    loss_f.run();
+    loss_f.back();
+
+    let grads = Rc::new(RefCell::new(Vec::new()));
+    let mut optim = RunningOptimizer::new(GradOptimizer {
+        grads: Rc::clone(&grads),
+    });
+    var.add_to_optimizer(&mut optim);
+    optim.step();
+    let grad = grads.borrow()[0].clone();
+
+    let mut placeholder = InputPlaceholder::new();
+    let mut numeric_f = (weights * f(placeholder.clone())).sum();
+
+    let eps = 1e-2;
+    for y in 0..input.height() {
+        for x in 0..input.width() {
+            let mut plus = input.clone();
+            plus[y][x] += eps;
+            placeholder.set_input(plus);
+            let loss_plus = numeric_f.run()[0][0];
+
+            let mut minus = input.clone();
+            minus[y][x] -= eps;
+            placeholder.set_input(minus);
+            let loss_minus = numeric_f.run()[0][0];
+
+            let numeric = (loss_plus - loss_minus) / (2.0 * eps);
+            assert!(
+                (numeric - grad[y][x]).abs() < 1e-2,
+                "numeric {} analytic {}",
+                numeric,
+                grad[y][x]
+            );
+        }
+    }
+}
+
+#[test]
+fn sigmoid() {
+    check_grad(|op| op.sigmoid());
+}
+
+#[test]
+fn tanh() {
+    check_grad(|op| op.tanh());
+}
+
+#[test]
+fn exp() {
+    check_grad(|op| op.exp());
+}
+
+#[test]
+fn softmax() {
+    check_grad(|op| op.softmax(Axis::Height));
+    check_grad(|op| op.log_softmax(Axis::Width));
+}
@@ -0,0 +1,94 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::input::InputPlaceholder,
+    optim::{
+        adam::{AdamOptimizerRunner, AdamWOptimizerRunner},
+        Optimizer, RunningOptimizer,
+    },
+};
+
+fn assert_close(expected: f32, actual: f32) {
+    assert!(
+        (expected - actual).abs() < 1e-5,
+        "expected {} got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn first_step() {
+    let var = Matrix::new(1, 2, vec![1.0, -2.0]).as_variable();
+
+    let mut result_op = var.clone().times(3.0).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(AdamOptimizerRunner::new(0.1));
+    var.add_to_optimizer(&mut optim);
+    optim.step();
+
+    // Bias corrected moments make the first update exactly `lr` in the gradient direction.
+    let value = var.get_output();
+    assert_close(0.9, value[0][0]);
+    assert_close(-2.1, value[0][1]);
+}
+
+#[test]
+fn late_variable_first_step() {
+    let var0 = Matrix::new(1, 1, vec![1.0]).as_variable();
+    let var1 = Matrix::new(1, 1, vec![1.0]).as_variable();
+
+    let mut result_op = (var0.clone() + var1.clone()).times(3.0).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(AdamOptimizerRunner::new(0.1));
+    var0.add_to_optimizer(&mut optim);
+    for _ in 0..3 {
+        optim.step();
+    }
+
+    // A variable registered later gets its own bias correction, its first update is `lr` too.
+    var1.add_to_optimizer(&mut optim);
+    optim.step();
+    assert_close(0.9, var1.get_output()[0][0]);
+}
+
+#[test]
+fn first_step_decoupled_weight_decay() {
+    let var = Matrix::new(1, 2, vec![1.0, -2.0]).as_variable();
+
+    let mut result_op = var.clone().times(3.0).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(AdamWOptimizerRunner::new(0.1, 0.5));
+    var.add_to_optimizer(&mut optim);
+    optim.step();
+
+    let value = var.get_output();
+    assert_close(1.0 * 0.95 - 0.1, value[0][0]);
+    assert_close(-2.0 * 0.95 - 0.1, value[0][1]);
+}
+
+#[test]
+fn converge() {
+    let target = InputPlaceholder::with_value(Matrix::new(1, 3, vec![3.0, -1.0, 0.5]));
+    let var = Matrix::zeros(1, 3).as_variable();
+
+    let mut loss_f = (target - var.clone()).pow(2.0).sum();
+
+    let mut optim = RunningOptimizer::new(AdamOptimizerRunner::new(0.05).with_betas(0.8, 0.99));
+    var.add_to_optimizer(&mut optim);
+
+    for _ in 0..1000 {
+        loss_f.run();
+        loss_f.back();
+        optim.step();
+        optim.zero_grad();
+    }
+
+    assert!(loss_f.run()[0][0] < 1e-3);
+}
@@ -28,7 +28,7 @@ struct TestOptimizer {
 }
 
 impl OptimizerRunner for TestOptimizer {
-    fn run(&mut self, variables: Vec<(&mut Matrix, &mut Matrix)>) {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
         for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
             assert_eq!(expected_grad.height(), grad.height());
             assert_eq!(expected_grad.width(), grad.width());
@@ -40,6 +40,12 @@ impl OptimizerRunner for TestOptimizer {
             }
         }
     }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
 }
 
 #[test]
@@ -0,0 +1,84 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::input::InputPlaceholder,
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+#[test]
+fn run() {
+    let mat0 = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let placeholder0 = InputPlaceholder::with_value(mat0.clone());
+
+    let mat1 = Matrix::new(2, 1, vec![10.0, 20.0]);
+    let placeholder1 = InputPlaceholder::with_value(mat1.clone());
+
+    let mat2 = Matrix::new(1, 3, vec![1.0, 2.0, 3.0]);
+    let placeholder2 = InputPlaceholder::with_value(mat2.clone());
+
+    let mut result_op =
+        (placeholder0.clone() + placeholder1.clone()) * placeholder2.clone() - placeholder1.clone();
+    let result = result_op.run();
+
+    assert_eq!(2, result.height());
+    assert_eq!(3, result.width());
+    for y in 0..result.height() {
+        for x in 0..result.width() {
+            assert_eq!(
+                (mat0[y][x] + mat1[y][0]) * mat2[0][x] - mat1[y][0],
+                result[y][x]
+            );
+        }
+    }
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_eq!(expected_grad.height(), grad.height());
+            assert_eq!(expected_grad.width(), grad.width());
+
+            for y in 0..grad.height() {
+                for x in 0..grad.width() {
+                    assert_eq!(expected_grad[y][x], grad[y][x]);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back() {
+    let mat0 = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let var0 = mat0.clone().as_variable();
+
+    let var1 = Matrix::new(2, 1, vec![10.0, 20.0]).as_variable();
+    let var2 = Matrix::new(1, 3, vec![1.0, 2.0, 3.0]).as_variable();
+
+    let mut result_op = ((var0.clone() + var1.clone()) * var2.clone()).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![
+            Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]),
+            Matrix::new(2, 1, vec![6.0, 6.0]),
+            Matrix::new(1, 3, vec![33.0, 35.0, 37.0]),
+        ],
+    });
+    var0.add_to_optimizer(&mut optim);
+    var1.add_to_optimizer(&mut optim);
+    var2.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
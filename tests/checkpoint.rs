@@ -0,0 +1,82 @@
+use std::{env, fs, process};
+
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::Matrix,
+    operation::{input::InputPlaceholder, Operation},
+};
+
+fn model(input: &Operation, hidden: usize) -> Operation {
+    let weights0 = Matrix::randn(hidden, 3, 0.0, 1.0).as_variable();
+    let biases0 = Matrix::randn(hidden, 1, 0.0, 1.0).as_variable();
+    let weights1 = Matrix::randn(2, hidden, 0.0, 1.0).as_variable();
+
+    weights1.mmul((weights0.mmul(input.clone()) + biases0).sigmoid())
+}
+
+#[test]
+fn save_load() {
+    let path = env::temp_dir().join(format!("tenso_checkpoint_{}.bin", process::id()));
+    let input = Matrix::new(3, 1, vec![1.0, -2.0, 0.5]);
+
+    let mut saved_op = model(&InputPlaceholder::with_value(input.clone()), 4);
+    saved_op.save(&path).unwrap();
+
+    let mut loaded_op = model(&InputPlaceholder::with_value(input), 4);
+    loaded_op.run();
+    loaded_op.load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(saved_op.run() == loaded_op.run());
+    for (saved, loaded) in saved_op.parameters().iter().zip(loaded_op.parameters()) {
+        assert!(*saved.read() == *loaded.read());
+    }
+}
+
+#[test]
+fn invalid() {
+    let input_ph = InputPlaceholder::new();
+
+    let mut bytes = Vec::new();
+    model(&input_ph, 4).write_parameters(&mut bytes).unwrap();
+
+    let other_op = model(&input_ph, 5);
+    let before: Vec<Matrix> = other_op
+        .parameters()
+        .iter()
+        .map(|value| value.read().clone())
+        .collect();
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "load",
+            left: vec![2, 5],
+            right: vec![2, 4],
+        }),
+        other_op.read_parameters(&mut bytes.as_slice())
+    );
+    // A failed load leaves the graph untouched.
+    for (value, before) in other_op.parameters().iter().zip(before) {
+        assert!(*value.read() == before);
+    }
+
+    let smaller_op = Matrix::zeros(2, 2).as_variable().sum();
+    assert_eq!(
+        Err(TensoError::ParameterCountMismatch {
+            expected: 1,
+            found: 3,
+        }),
+        smaller_op.read_parameters(&mut bytes.as_slice())
+    );
+
+    bytes[0] = b'X';
+    assert!(matches!(
+        smaller_op.read_parameters(&mut bytes.as_slice()),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+
+    assert!(matches!(
+        smaller_op.read_parameters(&mut &bytes[..2]),
+        Err(TensoError::Io { .. })
+    ));
+}
@@ -0,0 +1,219 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::Matrix,
+    operation::{custom::CustomOp, input::InputPlaceholder, Operation},
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+    tensor::Tensor,
+};
+
+/// `weights[0] * x0 + weights[1] * x1 + ...` for inputs of the same shape.
+struct WeightedSum {
+    weights: Vec<f32>,
+}
+
+impl CustomOp for WeightedSum {
+    fn check(&self, inputs: &[&Tensor]) -> Result<(), TensoError> {
+        for input in inputs {
+            if input.shape() != inputs[0].shape() {
+                return Err(TensoError::ShapeMismatch {
+                    op: "weighted_sum",
+                    left: inputs[0].shape().to_vec(),
+                    right: input.shape().to_vec(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn forward(&self, inputs: &[&Tensor]) -> Tensor {
+        let mut result = Tensor::zeros(inputs[0].shape().to_vec());
+        for (input, weight) in inputs.iter().zip(&self.weights) {
+            result = result.zip_map(input, |v_sum, v| v_sum + weight * v);
+        }
+        result
+    }
+
+    fn backward(&self, _: &[&Tensor], _: &Tensor, grad: &Tensor) -> Vec<Tensor> {
+        self.weights
+            .iter()
+            .map(|weight| grad.map(|v| weight * v))
+            .collect()
+    }
+}
+
+#[test]
+fn run() {
+    let placeholder0 = InputPlaceholder::with_value(Matrix::new(1, 2, vec![1.0, 2.0]));
+    let placeholder1 = InputPlaceholder::with_value(Matrix::new(1, 2, vec![3.0, 4.0]));
+
+    let mut result_op = Operation::custom(
+        &[placeholder0.clone(), placeholder1.clone(), placeholder0],
+        WeightedSum {
+            weights: vec![1.0, 10.0, 100.0],
+        },
+    );
+
+    let result = result_op.run();
+    assert_eq!(131.0, result[0][0]);
+    assert_eq!(242.0, result[0][1]);
+
+    let mut invalid_op = Operation::custom(
+        &[
+            placeholder1,
+            InputPlaceholder::with_value(Matrix::zeros(2, 1)),
+        ],
+        WeightedSum {
+            weights: vec![1.0, 1.0],
+        },
+    );
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "weighted_sum",
+            left: vec![1, 2],
+            right: vec![2, 1],
+        }),
+        invalid_op.try_run().map(|_| ())
+    );
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_eq!(expected_grad.height(), grad.height());
+            assert_eq!(expected_grad.width(), grad.width());
+
+            for y in 0..grad.height() {
+                for x in 0..grad.width() {
+                    assert_eq!(expected_grad[y][x], grad[y][x]);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back() {
+    let var0 = Matrix::new(1, 2, vec![1.0, 2.0]).as_variable();
+    let var1 = Matrix::new(1, 2, vec![3.0, 4.0]).as_variable();
+
+    let mut result_op = Operation::custom(
+        &[var0.clone(), var1.clone(), var0.clone()],
+        WeightedSum {
+            weights: vec![1.0, 10.0, 100.0],
+        },
+    )
+    .pow(2.0)
+    .sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![
+            Matrix::new(1, 2, vec![2.0 * 131.0 * 101.0, 2.0 * 242.0 * 101.0]),
+            Matrix::new(1, 2, vec![2.0 * 131.0 * 10.0, 2.0 * 242.0 * 10.0]),
+        ],
+    });
+    result_op.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
+
+/// Constant output counting its forward passes.
+struct Counter {
+    runs: Arc<AtomicUsize>,
+}
+
+impl CustomOp for Counter {
+    fn forward(&self, _: &[&Tensor]) -> Tensor {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Tensor::scalar(1.0)
+    }
+
+    fn backward(&self, _: &[&Tensor], _: &Tensor, _: &Tensor) -> Vec<Tensor> {
+        Vec::new()
+    }
+}
+
+#[test]
+fn cache_without_inputs() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut result_op = Operation::custom(&[], Counter { runs: runs.clone() });
+
+    result_op.run();
+    result_op.run();
+    assert_eq!(1, runs.load(Ordering::SeqCst));
+}
+
+/// Returns gradients of a fixed shape, whatever the shape of the inputs.
+struct WrongGrad {
+    grads: Vec<Vec<usize>>,
+}
+
+impl CustomOp for WrongGrad {
+    fn forward(&self, _: &[&Tensor]) -> Tensor {
+        Tensor::scalar(0.0)
+    }
+
+    fn backward(&self, _: &[&Tensor], _: &Tensor, _: &Tensor) -> Vec<Tensor> {
+        self.grads
+            .iter()
+            .map(|shape| Tensor::zeros(shape.clone()))
+            .collect()
+    }
+}
+
+#[test]
+fn wrong_grad_count() {
+    let var = Matrix::new(1, 2, vec![1.0, 2.0]).as_variable();
+    let mut result_op = Operation::custom(&[var], WrongGrad { grads: Vec::new() });
+
+    result_op.run();
+    assert!(matches!(
+        result_op.try_back(),
+        Err(TensoError::GradientCountMismatch {
+            op,
+            expected: 1,
+            found: 0,
+        }) if op.ends_with("WrongGrad")
+    ));
+
+    // The error leaves the graph usable.
+    result_op.run();
+}
+
+#[test]
+fn wrong_grad_shape() {
+    let var = Matrix::new(1, 2, vec![1.0, 2.0]).as_variable();
+    let mut result_op = Operation::custom(
+        &[var],
+        WrongGrad {
+            grads: vec![vec![2, 1]],
+        },
+    );
+
+    result_op.run();
+    assert!(matches!(
+        result_op.try_back(),
+        Err(TensoError::ShapeMismatch {
+            op,
+            left,
+            right,
+        }) if op.ends_with("WrongGrad") && left == [1, 2] && right == [2, 1]
+    ));
+}
@@ -0,0 +1,76 @@
+use tenso_rs::{self, error::TensoError, matrix::Matrix, operation::input::InputPlaceholder};
+
+#[test]
+fn shape_mismatch() {
+    let placeholder0 = InputPlaceholder::with_value(Matrix::zeros(3, 2));
+    let placeholder1 = InputPlaceholder::with_value(Matrix::zeros(2, 3));
+
+    let mut add_op = placeholder0.clone() + placeholder1.clone();
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "add",
+            left: vec![3, 2],
+            right: vec![2, 3],
+        }),
+        add_op.try_run().map(|_| ())
+    );
+
+    let mut mmul_op = placeholder0.clone().mmul(placeholder0.clone());
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "mmul",
+            left: vec![3, 2],
+            right: vec![3, 2],
+        }),
+        mmul_op.try_run().map(|_| ())
+    );
+
+    let mut valid_op = placeholder0.clone().mmul(placeholder1.clone());
+    assert!(valid_op.try_run().is_ok());
+}
+
+#[test]
+fn empty_input() {
+    let mut mean_op = InputPlaceholder::new().mean();
+    assert_eq!(
+        Err(TensoError::EmptyInput { op: "mean" }),
+        mean_op.try_run().map(|_| ())
+    );
+}
+
+#[test]
+fn non_scalar_backward() {
+    let placeholder = InputPlaceholder::with_value(Matrix::zeros(3, 2));
+
+    let mut result_op = placeholder.clone().times(2.0);
+    result_op.run();
+    assert_eq!(
+        Err(TensoError::NonScalarBackward { shape: vec![3, 2] }),
+        result_op.try_back()
+    );
+
+    let mut result_op = result_op.sum();
+    result_op.run();
+    assert_eq!(Ok(()), result_op.try_back());
+}
+
+#[test]
+#[should_panic(expected = "Shape mismatch in mul: 3x2 and 2x3")]
+fn run_panics() {
+    let placeholder0 = InputPlaceholder::with_value(Matrix::zeros(3, 2));
+    let placeholder1 = InputPlaceholder::with_value(Matrix::zeros(2, 3));
+
+    (placeholder0 * placeholder1).run();
+}
+
+#[test]
+#[should_panic]
+fn matrix_zip_shape_mismatch() {
+    Matrix::zeros(3, 2).zip_map(&Matrix::zeros(2, 3), |a, b| a + b);
+}
+
+#[test]
+#[should_panic]
+fn matrix_reshape_size_mismatch() {
+    Matrix::zeros(3, 2).reshape(4, 2);
+}
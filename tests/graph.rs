@@ -0,0 +1,121 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::input::InputPlaceholder,
+    optim::{sgd::SGDOptimizerRunner, Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+#[test]
+fn run_shared() {
+    let mat = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let placeholder = InputPlaceholder::with_value(mat.clone());
+
+    let shared = placeholder.clone().times(2.0);
+    let mut result_op = shared.clone() + shared.clone() * shared.clone();
+
+    let result = result_op.run();
+    for y in 0..result.height() {
+        for x in 0..result.width() {
+            let v = 2.0 * mat[y][x];
+            assert_eq!(v + v * v, result[y][x]);
+        }
+    }
+}
+
+#[test]
+fn run_diamond() {
+    let mut placeholder = InputPlaceholder::with_value(Matrix::from_const(1, 1, 1.0));
+
+    // Without evaluating shared nodes once this graph would take 2^64 node evaluations.
+    let mut result_op = placeholder.clone();
+    for _ in 0..64 {
+        result_op = result_op.clone() + result_op.clone().times(0.0);
+    }
+
+    assert_eq!(1.0, result_op.run()[0][0]);
+
+    placeholder.set_input(Matrix::from_const(1, 1, 3.0));
+    assert_eq!(3.0, result_op.run()[0][0]);
+}
+
+#[test]
+fn run_updated_variable() {
+    let mut var = Matrix::from_const(1, 1, 2.0).as_variable();
+    let mut result_op = var.clone().pow(2.0).sum();
+    assert_eq!(4.0, result_op.run()[0][0]);
+
+    // In-place optimizer updates are seen by the next run.
+    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(0.25));
+    result_op.add_to_optimizer(&mut optim);
+    result_op.back();
+    optim.step();
+    assert_eq!(1.0, result_op.run()[0][0]);
+
+    var.set_input(Matrix::from_const(1, 1, 3.0));
+    assert_eq!(9.0, result_op.run()[0][0]);
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        assert_eq!(self.expected_grads.len(), variables.len());
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_eq!(expected_grad.height(), grad.height());
+            assert_eq!(expected_grad.width(), grad.width());
+
+            for y in 0..grad.height() {
+                for x in 0..grad.width() {
+                    assert_eq!(expected_grad[y][x], grad[y][x]);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back_shared() {
+    let mat = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let var = mat.clone().as_variable();
+
+    let shared = var.clone().times(2.0);
+    let mut result_op = (shared.clone() * shared.clone()).sum();
+
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::new(2, 2, vec![8.0, 16.0, 24.0, 32.0])],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
+
+#[test]
+fn back_diamond() {
+    let var = Matrix::from_const(1, 1, 1.0).as_variable();
+
+    let mut result_op = var.clone();
+    for _ in 0..64 {
+        result_op = result_op.clone() + result_op.clone().times(0.0);
+    }
+
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::from_const(1, 1, 1.0)],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
@@ -0,0 +1,109 @@
+use std::{env, fs, process};
+
+use tenso_rs::{
+    self,
+    data::idx::{ElementType, IdxFile},
+    error::TensoError,
+    matrix::Matrix,
+};
+
+fn idx_bytes(type_code: u8, dimensions: &[u32], data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0, 0, type_code, dimensions.len() as u8];
+    for dimension in dimensions {
+        bytes.extend(&dimension.to_be_bytes());
+    }
+    bytes.extend(data);
+    bytes
+}
+
+#[test]
+fn images() {
+    // Three 2x2 images.
+    let data: Vec<u8> = (0..12).collect();
+    let path = env::temp_dir().join(format!("tenso_idx_{}.idx3", process::id()));
+    fs::write(&path, idx_bytes(0x08, &[3, 2, 2], &data)).unwrap();
+    let images = IdxFile::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(ElementType::U8, images.element_type());
+    assert_eq!(&[3, 2, 2], images.dimensions());
+    assert_eq!(3, images.len());
+    assert_eq!(4, images.sample_size());
+
+    let samples: Vec<Matrix> = images.samples().collect();
+    assert_eq!(3, samples.len());
+    assert!(Matrix::new(4, 1, vec![4.0, 5.0, 6.0, 7.0]) == samples[1]);
+}
+
+#[test]
+fn labels() {
+    let labels = IdxFile::read(&mut &idx_bytes(0x08, &[3], &[2, 0, 1])[..]).unwrap();
+
+    let one_hot = labels.one_hot(3).unwrap();
+    assert!(Matrix::new(3, 1, vec![0.0, 0.0, 1.0]) == one_hot[0]);
+    assert!(Matrix::new(3, 1, vec![1.0, 0.0, 0.0]) == one_hot[1]);
+    assert!(Matrix::new(3, 1, vec![0.0, 1.0, 0.0]) == one_hot[2]);
+
+    assert!(matches!(
+        labels.one_hot(2),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+}
+
+#[test]
+fn element_types() {
+    let data: Vec<u8> = [-1i16, 300]
+        .iter()
+        .flat_map(|v| v.to_be_bytes().to_vec())
+        .collect();
+    let file = IdxFile::read(&mut &idx_bytes(0x0b, &[2], &data)[..]).unwrap();
+    assert_eq!(ElementType::I16, file.element_type());
+    assert!(Matrix::new(1, 1, vec![300.0]) == file.samples().nth(1).unwrap());
+
+    let data: Vec<u8> = [0.5f32, -2.0]
+        .iter()
+        .flat_map(|v| v.to_be_bytes().to_vec())
+        .collect();
+    let file = IdxFile::read(&mut &idx_bytes(0x0d, &[1, 2], &data)[..]).unwrap();
+    assert!(Matrix::new(2, 1, vec![0.5, -2.0]) == file.samples().next().unwrap());
+}
+
+#[test]
+fn invalid() {
+    let read = |bytes: Vec<u8>| IdxFile::read(&mut &bytes[..]).map(|_| ());
+
+    let mut bad_magic = idx_bytes(0x08, &[1], &[0]);
+    bad_magic[0] = 1;
+    assert!(matches!(
+        read(bad_magic),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert_eq!(
+        Err(TensoError::UnsupportedDtype {
+            dtype: "0x0a".to_string()
+        }),
+        read(idx_bytes(0x0a, &[1], &[0]))
+    );
+    assert_eq!(
+        Err(TensoError::UnsupportedRank { rank: 0 }),
+        read(idx_bytes(0x08, &[], &[]))
+    );
+    assert!(matches!(
+        read(idx_bytes(0x08, &[2, 3], &[0; 5])),
+        Err(TensoError::Io { .. })
+    ));
+    assert!(matches!(
+        read(idx_bytes(0x0e, &[u32::MAX; 3], &[0; 8])),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(idx_bytes(0x08, &[u32::MAX, 2], &[0; 8])),
+        Err(TensoError::Io { .. })
+    ));
+
+    let images = IdxFile::read(&mut &idx_bytes(0x08, &[1, 2], &[0, 1])[..]).unwrap();
+    assert_eq!(
+        Err(TensoError::UnsupportedRank { rank: 2 }),
+        images.one_hot(2).map(|_| ())
+    );
+}
@@ -29,7 +29,7 @@ struct TestOptimizer {
 }
 
 impl OptimizerRunner for TestOptimizer {
-    fn run(&mut self, variables: Vec<(&mut Matrix, &mut Matrix)>) {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
         for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
             assert_eq!(expected_grad.height(), grad.height());
             assert_eq!(expected_grad.width(), grad.width());
@@ -41,6 +41,12 @@ impl OptimizerRunner for TestOptimizer {
             }
         }
     }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
 }
 
 #[test]
@@ -98,3 +104,22 @@ fn back1() {
 
     optim.step();
 }
+
+#[test]
+fn blocked() {
+    // Sizes that are not multiples of the tile side exercise the partial blocks.
+    let mat0 = Matrix::randn(70, 130, 0.0, 1.0);
+    let mat1 = Matrix::randn(130, 65, 0.0, 1.0);
+
+    let result = mat0.mmul(&mat1);
+    let result_transposed = mat0.mmul_transposed(&mat1.transpose());
+    let result_lhs_transposed = mat0.transpose().transposed_mmul(&mat1);
+    for y in 0..result.height() {
+        for x in 0..result.width() {
+            let expected: f32 = (0..mat0.width()).map(|i| mat0[y][i] * mat1[i][x]).sum();
+            assert!((expected - result[y][x]).abs() < 1e-3);
+            assert!((expected - result_transposed[y][x]).abs() < 1e-3);
+            assert!((expected - result_lhs_transposed[y][x]).abs() < 1e-3);
+        }
+    }
+}
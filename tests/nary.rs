@@ -0,0 +1,132 @@
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::{Axis, Matrix},
+    operation::{input::InputPlaceholder, Operation},
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+fn assert_matrix(expected: &Matrix, actual: &Matrix) {
+    assert_eq!(expected.height(), actual.height());
+    assert_eq!(expected.width(), actual.width());
+
+    for y in 0..actual.height() {
+        for x in 0..actual.width() {
+            assert_eq!(expected[y][x], actual[y][x]);
+        }
+    }
+}
+
+#[test]
+fn run() {
+    let placeholder0 = InputPlaceholder::with_value(Matrix::new(1, 2, vec![1.0, 5.0]));
+    let placeholder1 = InputPlaceholder::with_value(Matrix::new(1, 2, vec![3.0, 4.0]));
+    let placeholder2 = InputPlaceholder::with_value(Matrix::new(2, 2, vec![6.0, 7.0, 8.0, 9.0]));
+
+    let mut concat_op = Operation::concat(
+        &[
+            placeholder0.clone(),
+            placeholder2.clone(),
+            placeholder1.clone(),
+        ],
+        Axis::Height,
+    );
+    assert_matrix(
+        &Matrix::new(4, 2, vec![1.0, 5.0, 6.0, 7.0, 8.0, 9.0, 3.0, 4.0]),
+        &concat_op.run(),
+    );
+
+    let mut concat_op = Operation::concat(
+        &[placeholder0.clone().transpose(), placeholder2.clone()],
+        Axis::Width,
+    );
+    assert_matrix(
+        &Matrix::new(2, 3, vec![1.0, 6.0, 7.0, 5.0, 8.0, 9.0]),
+        &concat_op.run(),
+    );
+
+    let mut stack_op = Operation::stack(&[placeholder0.clone(), placeholder1.clone()]);
+    assert_matrix(
+        &Matrix::new(2, 2, vec![1.0, 3.0, 5.0, 4.0]),
+        &stack_op.run(),
+    );
+
+    let mut add_op = Operation::add_n(&[
+        placeholder0.clone(),
+        placeholder1.clone(),
+        placeholder0.clone(),
+    ]);
+    assert_matrix(&Matrix::new(1, 2, vec![5.0, 14.0]), &add_op.run());
+
+    let mut max_op = Operation::max_n(&[placeholder0.clone(), placeholder1.clone()]);
+    assert_matrix(&Matrix::new(1, 2, vec![3.0, 5.0]), &max_op.run());
+
+    let mut invalid_op = Operation::add_n(&[placeholder0, placeholder2]);
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "add_n",
+            left: vec![1, 2],
+            right: vec![2, 2],
+        }),
+        invalid_op.try_run().map(|_| ())
+    );
+
+    let mut empty_op = Operation::concat(&[], Axis::Width);
+    assert_eq!(
+        Err(TensoError::EmptyInput { op: "concat" }),
+        empty_op.try_run().map(|_| ())
+    );
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_matrix(expected_grad, grad);
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back() {
+    let var0 = Matrix::new(1, 2, vec![1.0, 5.0]).as_variable();
+    let var1 = Matrix::new(1, 2, vec![3.0, 4.0]).as_variable();
+    let var2 = Matrix::new(2, 2, vec![6.0, 7.0, 8.0, 9.0]).as_variable();
+
+    let weights = InputPlaceholder::with_value(Matrix::new(
+        4,
+        2,
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0],
+    ));
+
+    let concat = Operation::concat(&[var0.clone(), var2.clone(), var1.clone()], Axis::Height);
+    let max = Operation::max_n(&[var0.clone(), var1.clone()]);
+    let stack = Operation::stack(&[var0.clone(), var1.clone()]);
+    let mut result_op =
+        Operation::add_n(&[(concat * weights).sum(), max.sum(), stack.sum().times(10.0)]);
+
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![
+            Matrix::new(1, 2, vec![11.0, 13.0]),
+            Matrix::new(1, 2, vec![18.0, 18.0]),
+            Matrix::new(2, 2, vec![3.0, 4.0, 5.0, 6.0]),
+        ],
+    });
+    var0.add_to_optimizer(&mut optim);
+    var1.add_to_optimizer(&mut optim);
+    var2.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
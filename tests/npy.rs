@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, env, fs, process};
+
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::Matrix,
+    npy::{load_npz, save_npz},
+};
+
+/// `.npy` bytes the way NumPy writes them.
+fn npy_bytes(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        descr,
+        if fortran_order { "True" } else { "False" },
+        shape
+    );
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend(&(header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+#[test]
+fn read() {
+    let data: Vec<u8> = [1.5f64, -2.0, 3.25]
+        .iter()
+        .flat_map(|v| v.to_le_bytes().to_vec())
+        .collect();
+    let matrix = Matrix::read_npy(&mut &npy_bytes("<f8", false, "(3,)", &data)[..]).unwrap();
+    assert!(Matrix::new(3, 1, vec![1.5, -2.0, 3.25]) == matrix);
+
+    let data = 7.0f32.to_le_bytes();
+    let matrix = Matrix::read_npy(&mut &npy_bytes("<f4", false, "()", &data)[..]).unwrap();
+    assert!(Matrix::new(1, 1, vec![7.0]) == matrix);
+}
+
+#[test]
+fn write_read() {
+    let matrix = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let mut bytes = Vec::new();
+    matrix.write_npy(&mut bytes).unwrap();
+    assert_eq!(0, (bytes.len() - 6 * 4) % 64);
+    assert!(matrix == Matrix::read_npy(&mut &bytes[..]).unwrap());
+
+    let path = env::temp_dir().join(format!("tenso_npy_{}.npy", process::id()));
+    matrix.to_npy(&path).unwrap();
+    let loaded = Matrix::from_npy(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(matrix == loaded);
+}
+
+#[test]
+fn invalid() {
+    let data = [0; 8 * 4];
+    let read = |bytes: Vec<u8>| Matrix::read_npy(&mut &bytes[..]).map(|_| ());
+
+    assert_eq!(
+        Err(TensoError::UnsupportedDtype {
+            dtype: ">f4".to_string()
+        }),
+        read(npy_bytes(">f4", false, "(2, 4)", &data))
+    );
+    assert_eq!(
+        Err(TensoError::UnsupportedDtype {
+            dtype: "<i8".to_string()
+        }),
+        read(npy_bytes("<i8", false, "(2, 2)", &data))
+    );
+    assert_eq!(
+        Err(TensoError::UnsupportedRank { rank: 3 }),
+        read(npy_bytes("<f4", false, "(2, 2, 2)", &data))
+    );
+    assert!(matches!(
+        read(npy_bytes("<f4", true, "(2, 4)", &data)),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(b"PK\x03\x04 not a npy file".to_vec()),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(npy_bytes("<f4", false, "(4, 4)", &data)),
+        Err(TensoError::Io { .. })
+    ));
+    assert!(matches!(
+        read(npy_bytes(
+            "<f4",
+            false,
+            &format!("({}, 4)", usize::MAX),
+            &data
+        )),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(npy_bytes("<f4", false, "(2, 4), 'shape': (2, 4)", &data)),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    // Keys are only looked up as keys, not anywhere in the header text.
+    assert!(read(npy_bytes("<f4', 'x': 'shape", false, "(2, 4)", &data)).is_ok());
+}
+
+#[test]
+fn npz() {
+    let mut matrices = BTreeMap::new();
+    matrices.insert("weight".to_string(), Matrix::randn(4, 3, 0.0, 1.0));
+    matrices.insert("bias".to_string(), Matrix::randn(4, 1, 0.0, 1.0));
+
+    let path = env::temp_dir().join(format!("tenso_npz_{}.npz", process::id()));
+    save_npz(&path, &matrices).unwrap();
+    let loaded = load_npz(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(matrices.len(), loaded.len());
+    for (name, matrix) in matrices {
+        assert!(matrix == loaded[&name]);
+    }
+}
+
+#[test]
+fn npz_too_many_entries() {
+    let matrices: BTreeMap<String, Matrix> = (0..0xffff)
+        .map(|index| (index.to_string(), Matrix::zeros(1, 1)))
+        .collect();
+
+    let path = env::temp_dir().join(format!("tenso_npz_limit_{}.npz", process::id()));
+    let result = save_npz(&path, &matrices);
+    let _ = fs::remove_file(&path);
+    assert!(matches!(result, Err(TensoError::InvalidFormat { .. })));
+}
@@ -0,0 +1,113 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::input::InputPlaceholder,
+    optim::{sgd::SGDOptimizerRunner, GradClip, Optimizer, RunningOptimizer},
+};
+
+#[test]
+fn deduplicate_variables() {
+    let weights = Matrix::from_const(2, 2, 1.0).as_variable();
+    let biases = Matrix::zeros(2, 1).as_variable();
+    let input = InputPlaceholder::with_value(Matrix::from_const(2, 1, 1.0));
+
+    // Weight tying, the same weights and biases are applied twice.
+    let hidden = weights.clone().mmul(input) + biases.clone();
+    let mut loss_f = (weights.clone().mmul(hidden) + biases.clone()).sum();
+
+    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(0.1));
+    loss_f.add_to_optimizer(&mut optim);
+    weights.add_to_optimizer(&mut optim);
+
+    assert_eq!(vec![(2, 2), (2, 1)], optim.parameter_shapes());
+
+    loss_f.run();
+    loss_f.back();
+    optim.step();
+
+    // d/dw of sum(w * (w * 1 + b) + b) is 4 per element, d/db is 3.
+    let new_weights = weights.get_output();
+    assert!((new_weights[0][0] - 0.6).abs() < 1e-6);
+    let new_biases = biases.get_output();
+    assert!((new_biases[0][0] + 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn accumulate_gradients() {
+    let var = Matrix::from_const(1, 1, 1.0).as_variable();
+    let mut loss_f = var.clone().times(2.0).sum();
+
+    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(0.1));
+    loss_f.add_to_optimizer(&mut optim);
+
+    // Two micro-batches accumulate before a single step.
+    for _ in 0..2 {
+        loss_f.run();
+        loss_f.back();
+    }
+    optim.step();
+    assert!((var.get_output()[0][0] - 0.6).abs() < 1e-6);
+
+    // The step leaves the gradient in place until it is zeroed.
+    optim.step();
+    assert!((var.get_output()[0][0] - 0.2).abs() < 1e-6);
+
+    optim.zero_grad();
+    optim.step();
+    assert!((var.get_output()[0][0] - 0.2).abs() < 1e-6);
+}
+
+#[test]
+fn clip_grad_norm() {
+    let var = Matrix::new(1, 2, vec![0.0, 0.0]).as_variable();
+    let weights = InputPlaceholder::with_value(Matrix::new(1, 2, vec![3.0, 4.0]));
+    let mut loss_f = (var.clone() * weights).sum();
+
+    let mut optim =
+        RunningOptimizer::new(SGDOptimizerRunner::new(1.0)).with_grad_clip(GradClip::Norm(1.0));
+    loss_f.add_to_optimizer(&mut optim);
+
+    loss_f.run();
+    loss_f.back();
+    optim.step();
+
+    assert_eq!(Some(5.0), optim.last_grad_norm());
+    let value = var.get_output();
+    assert!((value[0][0] + 0.6).abs() < 1e-6);
+    assert!((value[0][1] + 0.8).abs() < 1e-6);
+
+    // The accumulated gradient is left unclipped for the next step.
+    optim.step();
+    assert_eq!(Some(5.0), optim.last_grad_norm());
+}
+
+#[test]
+#[should_panic]
+fn clip_grad_rejects_negative_bound() {
+    RunningOptimizer::new(SGDOptimizerRunner::new(1.0)).with_grad_clip(GradClip::Value(-1.0));
+}
+
+#[test]
+#[should_panic]
+fn clip_grad_rejects_nan_bound() {
+    RunningOptimizer::new(SGDOptimizerRunner::new(1.0)).with_grad_clip(GradClip::Norm(f32::NAN));
+}
+
+#[test]
+fn clip_grad_value() {
+    let var = Matrix::new(1, 2, vec![0.0, 0.0]).as_variable();
+    let weights = InputPlaceholder::with_value(Matrix::new(1, 2, vec![0.5, -4.0]));
+    let mut loss_f = (var.clone() * weights).sum();
+
+    let mut optim =
+        RunningOptimizer::new(SGDOptimizerRunner::new(1.0)).with_grad_clip(GradClip::Value(1.0));
+    loss_f.add_to_optimizer(&mut optim);
+
+    loss_f.run();
+    loss_f.back();
+    optim.step();
+
+    let value = var.get_output();
+    assert!((value[0][0] + 0.5).abs() < 1e-6);
+    assert!((value[0][1] - 1.0).abs() < 1e-6);
+}
@@ -0,0 +1,44 @@
+use tenso_rs::{self, matrix::Matrix};
+
+// Large enough to go past the threshold of the parallel kernels when the feature is enabled.
+const HEIGHT: usize = 300;
+const WIDTH: usize = 200;
+
+#[test]
+fn map() {
+    let mat = Matrix::randn(HEIGHT, WIDTH, 0.0, 1.0);
+
+    let result = mat.map(|v| 2.0 * v + 1.0);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            assert_eq!(2.0 * mat[y][x] + 1.0, result[y][x]);
+        }
+    }
+
+    let mut applied = mat.clone();
+    applied.apply(|v| 2.0 * v + 1.0);
+    assert!(applied == result);
+}
+
+#[test]
+fn zip_map() {
+    let mat0 = Matrix::randn(HEIGHT, WIDTH, 0.0, 1.0);
+    let mat1 = Matrix::randn(HEIGHT, WIDTH, 0.0, 1.0);
+
+    let result = mat0.zip_map(&mat1, |v0, v1| v0 * v1);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            assert_eq!(mat0[y][x] * mat1[y][x], result[y][x]);
+        }
+    }
+}
+
+#[test]
+fn sum() {
+    let mat = Matrix::from_const(HEIGHT, WIDTH, 0.5);
+    assert_eq!((HEIGHT * WIDTH) as f32 * 0.5, mat.sum());
+
+    let mat = Matrix::randn(HEIGHT, WIDTH, 0.0, 1.0);
+    let expected: f64 = mat.chain_data(|data_iter| data_iter.map(|v| *v as f64).sum());
+    assert!((expected as f32 - mat.sum()).abs() < 1e-2);
+}
@@ -0,0 +1,104 @@
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::{Axis, Matrix},
+    operation::{input::InputPlaceholder, Operation},
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+fn assert_matrix(expected: &Matrix, actual: &Matrix) {
+    assert_eq!(expected.height(), actual.height());
+    assert_eq!(expected.width(), actual.width());
+
+    for y in 0..actual.height() {
+        for x in 0..actual.width() {
+            assert_eq!(expected[y][x], actual[y][x]);
+        }
+    }
+}
+
+#[test]
+fn run() {
+    let input = Matrix::new(2, 3, vec![1.0, 6.0, 2.0, 4.0, 3.0, 8.0]);
+    let placeholder = InputPlaceholder::with_value(input.clone());
+
+    let mut sum_op = placeholder.clone().sum_axis(Axis::Height, true);
+    assert_matrix(&Matrix::new(1, 3, vec![5.0, 9.0, 10.0]), &sum_op.run());
+
+    let mut sum_op = placeholder.clone().sum_axis(Axis::Height, false);
+    assert_matrix(&Matrix::new(3, 1, vec![5.0, 9.0, 10.0]), &sum_op.run());
+
+    let mut mean_op = placeholder.clone().mean_axis(Axis::Width, true);
+    assert_matrix(&Matrix::new(2, 1, vec![3.0, 5.0]), &mean_op.run());
+
+    let mut max_op = placeholder.clone().max_axis(Axis::Width, false);
+    assert_matrix(&Matrix::new(2, 1, vec![6.0, 8.0]), &max_op.run());
+
+    let mut min_op = placeholder.clone().min_axis(Axis::Height, true);
+    assert_matrix(&Matrix::new(1, 3, vec![1.0, 3.0, 2.0]), &min_op.run());
+
+    let mut argmax_op = placeholder.clone().argmax(Axis::Height);
+    assert_matrix(&Matrix::new(3, 1, vec![1.0, 0.0, 1.0]), &argmax_op.run());
+
+    assert_eq!(vec![1, 2], input.argmax(Axis::Width));
+
+    let mut empty_op =
+        InputPlaceholder::with_value(Matrix::zeros(0, 2)).max_axis(Axis::Width, true);
+    assert_eq!(
+        Err(TensoError::EmptyInput { op: "max_axis" }),
+        empty_op.try_run().map(|_| ())
+    );
+
+    let mut empty_argmax_op =
+        InputPlaceholder::with_value(Matrix::zeros(0, 3)).argmax(Axis::Height);
+    assert_eq!(
+        Err(TensoError::EmptyInput { op: "argmax" }),
+        empty_argmax_op.try_run().map(|_| ())
+    );
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_matrix(expected_grad, grad);
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back() {
+    let var = Matrix::new(2, 3, vec![1.0, 6.0, 2.0, 4.0, 3.0, 8.0]).as_variable();
+    let weights = InputPlaceholder::with_value(Matrix::new(1, 3, vec![1.0, 2.0, 3.0]));
+
+    let mut result_op = Operation::add_n(&[
+        (var.clone().sum_axis(Axis::Height, true) * weights).sum(),
+        var.clone().mean_axis(Axis::Width, false).sum().times(3.0),
+        var.clone().max_axis(Axis::Width, true).sum().times(10.0),
+        var.clone().min_axis(Axis::Height, false).sum().times(100.0),
+        var.clone().argmax(Axis::Width).sum(),
+    ]);
+
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::new(
+            2,
+            3,
+            vec![102.0, 13.0, 104.0, 2.0, 103.0, 14.0],
+        )],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
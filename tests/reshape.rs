@@ -0,0 +1,85 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::input::InputPlaceholder,
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+#[test]
+fn matrix() {
+    let mat = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    let transposed = mat.transpose();
+    assert_eq!(3, transposed.height());
+    assert_eq!(2, transposed.width());
+    assert_eq!(mat[0][2], transposed[2][0]);
+    assert_eq!(mat[1][0], transposed[0][1]);
+
+    let reshaped = mat.clone().reshape(3, 2);
+    assert_eq!(mat[1][0], reshaped[1][1]);
+
+    let flat = mat.clone().flatten();
+    assert_eq!(6, flat.height());
+    assert_eq!(1, flat.width());
+    assert_eq!(mat[1][2], flat[5][0]);
+}
+
+#[test]
+fn run() {
+    let mat = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let placeholder = InputPlaceholder::with_value(mat.clone());
+
+    let mut result_op = placeholder.clone().transpose().reshape(2, 3);
+    let result = result_op.run();
+
+    let expected = Matrix::new(2, 3, vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+    for y in 0..result.height() {
+        for x in 0..result.width() {
+            assert_eq!(expected[y][x], result[y][x]);
+        }
+    }
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_eq!(expected_grad.height(), grad.height());
+            assert_eq!(expected_grad.width(), grad.width());
+
+            for y in 0..grad.height() {
+                for x in 0..grad.width() {
+                    assert_eq!(expected_grad[y][x], grad[y][x]);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn back() {
+    let var = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]).as_variable();
+    let weights =
+        InputPlaceholder::with_value(Matrix::new(1, 6, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+
+    let mut result_op = weights.mmul(var.clone().transpose().flatten()).sum();
+    result_op.run();
+    result_op.back();
+
+    // Flattened transpose is [v00, v10, v01, v11, v02, v12].
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::new(2, 3, vec![1.0, 3.0, 5.0, 2.0, 4.0, 6.0])],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, env, fs, process};
+
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::Matrix,
+    operation::{input::InputPlaceholder, Operation},
+    safetensors::{read_safetensors, write_safetensors},
+};
+
+fn safetensors_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend(header.as_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+#[test]
+fn read() {
+    let header = r#"{
+        "__metadata__": {"format": "pt"},
+        "half": {"dtype": "F16", "shape": [3], "data_offsets": [0, 6]},
+        "brain": {"dtype": "BF16", "shape": [1, 2], "data_offsets": [6, 10]},
+        "double": {"dtype": "F64", "shape": [], "data_offsets": [10, 18]}
+    }"#;
+    let mut data = Vec::new();
+    for bits in [0x3c00u16, 0xc100, 0x0001, 0x3f80, 0x3f00].iter() {
+        data.extend(&bits.to_le_bytes());
+    }
+    data.extend(&3.5f64.to_le_bytes());
+
+    let matrices = read_safetensors(&safetensors_bytes(header, &data)).unwrap();
+    assert_eq!(3, matrices.len());
+    assert!(Matrix::new(3, 1, vec![1.0, -2.5, 2f32.powi(-24)]) == matrices["half"]);
+    assert!(Matrix::new(1, 2, vec![1.0, 0.5]) == matrices["brain"]);
+    assert!(Matrix::new(1, 1, vec![3.5]) == matrices["double"]);
+}
+
+#[test]
+fn write_read() {
+    let mut matrices = BTreeMap::new();
+    matrices.insert("layer.weight".to_string(), Matrix::randn(3, 2, 0.0, 1.0));
+    matrices.insert("quoted \"name\"".to_string(), Matrix::randn(1, 4, 0.0, 1.0));
+
+    let mut bytes = Vec::new();
+    write_safetensors(&mut bytes, &matrices).unwrap();
+
+    let header_len = u64::from_le_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ]) as usize;
+    assert_eq!(0, header_len % 8);
+    assert_eq!(8 + header_len + 10 * 4, bytes.len());
+
+    let loaded = read_safetensors(&bytes).unwrap();
+    assert_eq!(matrices.len(), loaded.len());
+    for (name, matrix) in matrices {
+        assert!(matrix == loaded[&name]);
+    }
+}
+
+#[test]
+fn operation() {
+    let input_ph = InputPlaceholder::with_value(Matrix::new(2, 1, vec![1.0, -1.0]));
+    let model = |input: &Operation| {
+        Matrix::randn(3, 2, 0.0, 1.0)
+            .as_named_variable("weight")
+            .mmul(input.clone())
+            + Matrix::randn(3, 1, 0.0, 1.0).as_named_variable("bias")
+    };
+
+    let mut saved_op = model(&input_ph);
+    let mut loaded_op = model(&input_ph);
+
+    let path = env::temp_dir().join(format!("tenso_safetensors_{}.bin", process::id()));
+    saved_op.save_safetensors(&path).unwrap();
+    loaded_op.load_safetensors(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(saved_op.run() == loaded_op.run());
+}
+
+#[test]
+fn invalid() {
+    let data = [0; 16];
+    let read = |header: &str| read_safetensors(&safetensors_bytes(header, &data)).map(|_| ());
+
+    assert_eq!(
+        Err(TensoError::UnsupportedDtype {
+            dtype: "I32".to_string()
+        }),
+        read(r#"{"x": {"dtype": "I32", "shape": [2], "data_offsets": [0, 8]}}"#)
+    );
+    assert_eq!(
+        Err(TensoError::UnsupportedRank { rank: 3 }),
+        read(r#"{"x": {"dtype": "F32", "shape": [1, 2, 2], "data_offsets": [0, 16]}}"#)
+    );
+    assert!(matches!(
+        read(r#"{"x": {"dtype": "F32", "shape": [2, 2], "data_offsets": [0, 8]}}"#),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(r#"{"x": {"dtype": "F32", "shape": [2, 4], "data_offsets": [0, 32]}}"#),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read(&format!(
+            r#"{{"x": {{"dtype": "F32", "shape": [{}, 4], "data_offsets": [0, 16]}}}}"#,
+            usize::MAX
+        )),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert_eq!(
+        Err(TensoError::InvalidFormat {
+            reason: "duplicate tensor x".to_string()
+        }),
+        read(concat!(
+            r#"{"x": {"dtype": "F32", "shape": [2], "data_offsets": [0, 8]},"#,
+            r#" "x": {"dtype": "F32", "shape": [2], "data_offsets": [8, 16]}}"#
+        ))
+    );
+    assert!(matches!(
+        read(r#"{"x": {"dtype": "F32", "shape": [2]"#),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+    assert!(matches!(
+        read_safetensors(&[1, 0]),
+        Err(TensoError::InvalidFormat { .. })
+    ));
+}
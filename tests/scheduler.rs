@@ -0,0 +1,85 @@
+use tenso_rs::optim::{
+    scheduler::{
+        CosineAnnealingWarmRestarts, ExponentialDecay, LinearWarmup, LrScheduler, ReduceOnPlateau,
+        StepDecay,
+    },
+    sgd::SGDOptimizerRunner,
+    Optimizer, RunningOptimizer,
+};
+
+fn assert_close(expected: f32, actual: f32) {
+    assert!(
+        (expected - actual).abs() < 1e-6,
+        "expected {} got {}",
+        expected,
+        actual
+    );
+}
+
+fn schedule(scheduler: &mut dyn LrScheduler, epochs: usize) -> Vec<f32> {
+    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(1.0));
+    (0..epochs)
+        .map(|_| {
+            scheduler.step(&mut optim);
+            optim.lr()
+        })
+        .collect()
+}
+
+fn assert_schedule(expected: &[f32], actual: &[f32]) {
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual) {
+        assert_close(*expected, *actual);
+    }
+}
+
+#[test]
+fn step_decay() {
+    let lrs = schedule(&mut StepDecay::new(2, 0.5), 5);
+    assert_schedule(&[1.0, 1.0, 0.5, 0.5, 0.25], &lrs);
+}
+
+#[test]
+#[should_panic]
+fn step_decay_zero_step_size() {
+    StepDecay::new(0, 0.5);
+}
+
+#[test]
+fn exponential_decay() {
+    let lrs = schedule(&mut ExponentialDecay::new(0.5), 4);
+    assert_schedule(&[1.0, 0.5, 0.25, 0.125], &lrs);
+}
+
+#[test]
+fn cosine_annealing_warm_restarts() {
+    let lrs = schedule(&mut CosineAnnealingWarmRestarts::new(2, 2, 0.0), 7);
+    assert_schedule(&[1.0, 0.5, 1.0, 0.853_553_4, 0.5, 0.146_446_6, 1.0], &lrs);
+}
+
+#[test]
+#[should_panic]
+fn cosine_annealing_zero_period() {
+    CosineAnnealingWarmRestarts::new(0, 2, 0.0);
+}
+
+#[test]
+fn linear_warmup() {
+    let lrs = schedule(&mut LinearWarmup::new(4, 0.2), 6);
+    assert_schedule(&[0.2, 0.4, 0.6, 0.8, 1.0, 1.0], &lrs);
+}
+
+#[test]
+fn reduce_on_plateau() {
+    let mut scheduler = ReduceOnPlateau::new(0.1, 1).with_min_lr(0.005);
+    let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(1.0));
+
+    let mut lrs = Vec::new();
+    for metric in &[5.0, 4.0, 4.0, 4.0, 3.0, 3.0, 3.0, 3.0, 3.0] {
+        scheduler.report(*metric);
+        scheduler.step(&mut optim);
+        lrs.push(optim.lr());
+    }
+
+    assert_schedule(&[1.0, 1.0, 1.0, 0.1, 0.1, 0.1, 0.01, 0.01, 0.005], &lrs);
+}
@@ -0,0 +1,79 @@
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    optim::{sgd::SGDOptimizerRunner, Optimizer, RunningOptimizer},
+};
+
+fn assert_close(expected: f32, actual: f32) {
+    assert!(
+        (expected - actual).abs() < 1e-5,
+        "expected {} got {}",
+        expected,
+        actual
+    );
+}
+
+// Runs three steps of minimizing `sum(2 * var)`, so the gradient is always 2.
+fn run_steps(runner: SGDOptimizerRunner) -> Vec<f32> {
+    let var = Matrix::from_const(1, 1, 1.0).as_variable();
+    let mut result_op = var.clone().times(2.0).sum();
+
+    let mut optim = RunningOptimizer::new(runner);
+    var.add_to_optimizer(&mut optim);
+
+    (0..3)
+        .map(|_| {
+            result_op.run();
+            result_op.back();
+            optim.step();
+            optim.zero_grad();
+
+            var.get_output()[0][0]
+        })
+        .collect()
+}
+
+#[test]
+fn plain() {
+    let values = run_steps(SGDOptimizerRunner::new(0.1));
+    assert_close(0.8, values[0]);
+    assert_close(0.6, values[1]);
+    assert_close(0.4, values[2]);
+}
+
+#[test]
+fn momentum() {
+    // Velocities: 2, 0.5 * 2 + 0.5 * 2 = 2, 2.
+    let values = run_steps(
+        SGDOptimizerRunner::new(0.1)
+            .with_momentum(0.5)
+            .with_dampening(0.5),
+    );
+    assert_close(0.8, values[0]);
+    assert_close(0.6, values[1]);
+
+    // Velocities: 2, 3, 3.5.
+    let values = run_steps(SGDOptimizerRunner::new(0.1).with_momentum(0.5));
+    assert_close(0.8, values[0]);
+    assert_close(0.5, values[1]);
+    assert_close(0.15, values[2]);
+}
+
+#[test]
+fn nesterov() {
+    // Steps: 2 + 0.5 * 2, 2 + 0.5 * 3, 2 + 0.5 * 3.5.
+    let values = run_steps(
+        SGDOptimizerRunner::new(0.1)
+            .with_momentum(0.5)
+            .with_nesterov(true),
+    );
+    assert_close(0.7, values[0]);
+    assert_close(0.35, values[1]);
+    assert_close(-0.025, values[2]);
+}
+
+#[test]
+fn weight_decay() {
+    let values = run_steps(SGDOptimizerRunner::new(0.1).with_weight_decay(1.0));
+    assert_close(1.0 - 0.1 * 3.0, values[0]);
+}
@@ -0,0 +1,111 @@
+use tenso_rs::{
+    self,
+    matrix::{Axis, Matrix},
+    operation::input::InputPlaceholder,
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+};
+
+fn assert_close(expected: f32, actual: f32) {
+    assert!(
+        (expected - actual).abs() < 1e-5,
+        "expected {} got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn run() {
+    let mat = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 1.0, 4.0, 1000.0]);
+
+    let mut softmax_op = InputPlaceholder::with_value(mat.clone()).softmax(Axis::Width);
+    let result = softmax_op.run();
+
+    let norm = 1.0 + 1f32.exp() + 2f32.exp();
+    assert_close(1.0 / norm, result[0][0]);
+    assert_close(2f32.exp() / norm, result[0][2]);
+    assert_close(1.0, result[1][2]);
+
+    let mut log_softmax_op = InputPlaceholder::with_value(mat.clone()).log_softmax(Axis::Height);
+    let result = log_softmax_op.run();
+
+    assert_close(-(2f32.ln()), result[0][0]);
+    assert_close(-(1.0 + (-2f32).exp()).ln(), result[1][1]);
+    assert_close(0.0, result[1][2]);
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert_eq!(expected_grad.height(), grad.height());
+            assert_eq!(expected_grad.width(), grad.width());
+
+            for y in 0..grad.height() {
+                for x in 0..grad.width() {
+                    assert_close(expected_grad[y][x], grad[y][x]);
+                }
+            }
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn cross_entropy() {
+    let logits = Matrix::new(3, 2, vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    let var = logits.clone().as_variable();
+
+    let targets = Matrix::new(3, 2, vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    let targets_ph = InputPlaceholder::with_value(targets);
+
+    let mut loss_op = var.clone().cross_entropy(targets_ph);
+    let loss = loss_op.run();
+    assert_close(3f32.ln(), loss[0][0]);
+
+    loss_op.back();
+
+    let third = 1.0 / 3.0;
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::new(
+            3,
+            2,
+            vec![
+                (third - 1.0) / 2.0,
+                third / 2.0,
+                third / 2.0,
+                third / 2.0,
+                third / 2.0,
+                (third - 1.0) / 2.0,
+            ],
+        )],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
+
+#[test]
+fn log_softmax_back() {
+    let var = Matrix::new(2, 1, vec![0.0, 0.0]).as_variable();
+
+    let mut result_op = var.clone().log_softmax(Axis::Height).times(-1.0).mean();
+    result_op.run();
+    result_op.back();
+
+    // Mean of -log_softmax over both classes does not depend on the logits.
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::zeros(2, 1)],
+    });
+    var.add_to_optimizer(&mut optim);
+
+    optim.step();
+}
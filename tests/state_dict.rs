@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::Matrix,
+    operation::{input::InputPlaceholder, Operation},
+};
+
+fn linear(input: &Operation, in_size: usize, out_size: usize, name: &str) -> Operation {
+    let weights =
+        Matrix::randn(out_size, in_size, 0.0, 1.0).as_named_variable(&format!("{}.weight", name));
+    let biases = Matrix::randn(out_size, 1, 0.0, 1.0).as_named_variable(&format!("{}.bias", name));
+
+    weights.mmul(input.clone()) + biases
+}
+
+fn model(input: &Operation) -> Operation {
+    linear(&linear(input, 3, 4, "layer0").sigmoid(), 4, 2, "layer1")
+}
+
+#[test]
+fn names() {
+    let input_ph = InputPlaceholder::named("input");
+    assert_eq!(Some("input".to_string()), input_ph.name());
+    assert_eq!(None, InputPlaceholder::new().name());
+    assert_eq!(None, input_ph.clone().sigmoid().name());
+
+    let state_dict = model(&input_ph).state_dict().unwrap();
+    assert_eq!(
+        vec![
+            "layer0.bias",
+            "layer0.weight",
+            "layer1.bias",
+            "layer1.weight"
+        ],
+        state_dict.keys().collect::<Vec<_>>()
+    );
+    assert_eq!((4, 3), state_dict["layer0.weight"].shape());
+    assert_eq!((2, 1), state_dict["layer1.bias"].shape());
+}
+
+#[test]
+fn load() {
+    let mut input_ph = InputPlaceholder::named("input");
+    input_ph.set_input(Matrix::new(3, 1, vec![1.0, -2.0, 0.5]));
+
+    let mut saved_op = model(&input_ph);
+    let mut loaded_op = model(&input_ph);
+    loaded_op.run();
+
+    loaded_op
+        .load_state_dict(&saved_op.state_dict().unwrap())
+        .unwrap();
+    assert!(saved_op.run() == loaded_op.run());
+
+    // Anonymous variables are not part of the state dict.
+    let anonymous_op = Matrix::zeros(2, 2).as_variable().sum();
+    assert!(anonymous_op.state_dict().unwrap().is_empty());
+    assert_eq!(Ok(()), anonymous_op.load_state_dict(&BTreeMap::new()));
+}
+
+#[test]
+fn invalid() {
+    let input_ph = InputPlaceholder::new();
+    let model_op = model(&input_ph);
+    let before = model_op.state_dict().unwrap();
+
+    let mut state_dict = before.clone();
+    state_dict.remove("layer1.bias");
+    assert_eq!(
+        Err(TensoError::MissingKey {
+            key: "layer1.bias".to_string()
+        }),
+        model_op.load_state_dict(&state_dict)
+    );
+
+    let mut state_dict = before.clone();
+    state_dict.insert("layer2.weight".to_string(), Matrix::zeros(1, 1));
+    assert_eq!(
+        Err(TensoError::UnexpectedKey {
+            key: "layer2.weight".to_string()
+        }),
+        model_op.load_state_dict(&state_dict)
+    );
+
+    let mut state_dict = before.clone();
+    state_dict.insert("layer0.weight".to_string(), Matrix::zeros(3, 4));
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "load_state_dict",
+            left: vec![4, 3],
+            right: vec![3, 4],
+        }),
+        model_op.load_state_dict(&state_dict)
+    );
+
+    // Failed loads leave the graph untouched.
+    assert!(before == model_op.state_dict().unwrap());
+}
+
+#[test]
+fn duplicate_names() {
+    let input_ph = InputPlaceholder::new();
+    let model_op = linear(&linear(&input_ph, 3, 4, "layer"), 4, 2, "layer");
+
+    assert_eq!(
+        Err(TensoError::DuplicateKey {
+            key: "layer.weight".to_string()
+        }),
+        model_op.state_dict().map(|_| ())
+    );
+    assert_eq!(
+        Err(TensoError::DuplicateKey {
+            key: "layer.weight".to_string()
+        }),
+        model_op.load_state_dict(&BTreeMap::new())
+    );
+}
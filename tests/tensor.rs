@@ -0,0 +1,293 @@
+use tenso_rs::{
+    self,
+    error::TensoError,
+    matrix::{Axis, Matrix},
+    operation::{input::InputPlaceholder, Operation},
+    optim::{Optimizer, OptimizerRunner, RunningOptimizer},
+    tensor::Tensor,
+};
+
+fn range(shape: Vec<usize>) -> Tensor {
+    let size = shape.iter().product::<usize>();
+    Tensor::new(shape, (0..size).map(|v| v as f32).collect())
+}
+
+struct TestOptimizer {
+    expected_grads: Vec<Matrix>,
+}
+
+impl OptimizerRunner for TestOptimizer {
+    fn run(&mut self, variables: Vec<(&mut Matrix, &Matrix)>) {
+        assert_eq!(self.expected_grads.len(), variables.len());
+        for ((_, grad), expected_grad) in variables.into_iter().zip(&self.expected_grads) {
+            assert!(expected_grad == grad);
+        }
+    }
+
+    fn lr(&self) -> f32 {
+        0.0
+    }
+
+    fn set_lr(&mut self, _: f32) {}
+}
+
+#[test]
+fn strides() {
+    let tensor = Tensor::new(vec![2, 3, 4], (0..24).map(|v| v as f32).collect());
+
+    assert_eq!(&[12, 4, 1], tensor.strides());
+    assert_eq!(3, tensor.rank());
+    assert_eq!(23.0, tensor[&[1, 2, 3][..]]);
+    assert_eq!(13.0, tensor[&[1, 0, 1][..]]);
+}
+
+#[test]
+fn to_matrix() {
+    let tensor = Tensor::new(vec![2, 3, 4], (0..24).map(|v| v as f32).collect());
+
+    let matrix: Matrix = tensor.clone().into();
+    assert_eq!(6, matrix.height());
+    assert_eq!(4, matrix.width());
+    assert_eq!(tensor[&[1, 2, 3][..]], matrix[5][3]);
+
+    let vector = Tensor::new(vec![3], vec![1.0, 2.0, 3.0]).to_matrix();
+    assert_eq!((3, 1), (vector.height(), vector.width()));
+}
+
+#[test]
+fn from_matrix() {
+    let matrix = Matrix::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let mut tensor = Tensor::from(matrix.clone());
+
+    assert_eq!(&[2, 3], tensor.shape());
+    assert_eq!(matrix[1][2], tensor[&[1, 2][..]]);
+
+    tensor[&[0, 1][..]] = 7.0;
+    let tensor = tensor.reshape(vec![3, 2]);
+    assert_eq!(7.0, tensor[&[0, 1][..]]);
+    assert_eq!(matrix[1][0], tensor[&[1, 1][..]]);
+}
+
+#[test]
+#[should_panic]
+fn invalid_shape() {
+    Tensor::new(vec![2, 3], vec![0.0; 5]);
+}
+
+#[test]
+#[should_panic]
+fn invalid_reshape() {
+    Tensor::zeros(vec![2, 3]).reshape(vec![4, 2]);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let tensor = Tensor::zeros(vec![2, 3]);
+    let _ = tensor[&[0, 3][..]];
+}
+
+#[test]
+fn broadcast() {
+    let left = range(vec![2, 1, 3]);
+    let right = Tensor::new(vec![4, 1], vec![0.0, 10.0, 20.0, 30.0]);
+
+    let result = left.broadcast_zip(&right, |v_left, v_right| v_left + v_right);
+    assert_eq!(&[2, 4, 3], result.shape());
+    assert_eq!(23.0, result[&[1, 2, 0][..]]);
+    assert_eq!(32.0, result[&[0, 3, 2][..]]);
+
+    let ones = Tensor::from_const(vec![2, 4, 3], 1.0);
+    assert!(Tensor::from_const(vec![4, 1], 6.0) == ones.sum_to_shape(&[4, 1]));
+    assert!(Tensor::from_const(vec![2, 1, 3], 4.0) == ones.sum_to_shape(&[2, 1, 3]));
+
+    // Vectors are columns.
+    let column = Tensor::zeros(vec![3]).broadcast_shape(&Tensor::zeros(vec![3, 1]));
+    assert_eq!(Some(vec![3, 1]), column);
+    assert_eq!(None, range(vec![2, 3]).broadcast_shape(&range(vec![3, 3])));
+}
+
+#[test]
+fn batched_matmul() {
+    let lhs = range(vec![2, 2, 3]);
+    let rhs = range(vec![3, 2]);
+
+    let result = lhs.matmul(&rhs);
+    assert_eq!(&[2, 2, 2], result.shape());
+    for batch in 0..2 {
+        let lhs_batch = Matrix::new(2, 3, (0..6).map(|v| (batch * 6 + v) as f32).collect());
+        let expected = lhs_batch.mmul(&rhs.to_matrix());
+        for y in 0..2 {
+            for x in 0..2 {
+                assert_eq!(expected[y][x], result[&[batch, y, x][..]]);
+            }
+        }
+    }
+
+    assert!(lhs.matmul(&rhs) == lhs.matmul_transposed(&rhs.transpose()));
+    assert!(lhs.matmul(&rhs) == lhs.transpose().transposed_matmul(&rhs));
+
+    let broadcast = range(vec![2, 1, 2, 3]).matmul_shape(&range(vec![3, 3, 2]));
+    assert_eq!(Some(vec![2, 3, 2, 2]), broadcast);
+    assert_eq!(None, range(vec![2, 3]).matmul_shape(&range(vec![2, 3])));
+}
+
+#[test]
+fn transpose() {
+    let tensor = range(vec![2, 2, 3]);
+    let transposed = tensor.transpose();
+
+    assert_eq!(&[2, 3, 2], transposed.shape());
+    assert_eq!(tensor[&[1, 0, 2][..]], transposed[&[1, 2, 0][..]]);
+    assert_eq!(&[1, 3], Tensor::zeros(vec![3]).transpose().shape());
+}
+
+#[test]
+fn concat_narrow() {
+    let left = range(vec![2, 1, 3]);
+    let right = range(vec![2, 2, 3]);
+
+    let joined = Tensor::concat(&[&left, &right], 1);
+    assert_eq!(&[2, 3, 3], joined.shape());
+    assert_eq!(left[&[1, 0, 2][..]], joined[&[1, 0, 2][..]]);
+    assert_eq!(right[&[1, 1, 0][..]], joined[&[1, 2, 0][..]]);
+
+    assert!(right == joined.narrow(1, 1, 2));
+}
+
+/*------------------------------------------------------------------------------------------------*/
+
+#[test]
+fn graph_batched_mmul() {
+    let input = range(vec![2, 2, 3]).as_variable();
+    let weight = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).as_variable();
+
+    let mut product_op = input.clone().mmul(weight.clone());
+    assert_eq!(&[2, 2, 2], product_op.run_tensor().shape());
+    assert_eq!((4, 2), product_op.run().shape());
+
+    let mut result_op = product_op.sum();
+    result_op.run();
+    result_op.back();
+
+    let input_grad = (0..4).flat_map(|_| vec![3.0, 7.0, 11.0]).collect();
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![
+            Matrix::new(4, 3, input_grad),
+            Matrix::new(3, 2, vec![18.0, 18.0, 22.0, 22.0, 26.0, 26.0]),
+        ],
+    });
+    result_op.add_to_optimizer(&mut optim);
+    optim.step();
+
+    assert_eq!((4, 3), result_op.parameters()[0].read().shape());
+    assert_eq!(&[2, 2, 3], input.get_output_tensor().shape());
+}
+
+#[test]
+fn graph_broadcast_add() {
+    let input = Tensor::from_const(vec![2, 3, 4], 1.0).as_variable();
+    let bias = Tensor::new(vec![3, 1], vec![1.0, 2.0, 3.0]).as_variable();
+
+    let mut result_op = (input + bias).sum();
+    assert_eq!(72.0, result_op.run()[0][0]);
+    assert_eq!(0, result_op.run_tensor().rank());
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::from_const(6, 4, 1.0), Matrix::from_const(3, 1, 8.0)],
+    });
+    result_op.add_to_optimizer(&mut optim);
+    optim.step();
+}
+
+#[test]
+fn graph_reduce_dim() {
+    let placeholder = InputPlaceholder::with_value(range(vec![2, 3, 4]));
+
+    let sum = placeholder.clone().sum_dim(1, false).run_tensor();
+    assert_eq!(&[2, 4], sum.shape());
+    assert_eq!(12.0, sum[&[0, 0][..]]);
+    assert_eq!(57.0, sum[&[1, 3][..]]);
+
+    let kept = placeholder.clone().sum_dim(0, true).run_tensor();
+    assert_eq!(&[1, 3, 4], kept.shape());
+    assert_eq!(12.0, kept[&[0, 0, 0][..]]);
+
+    // The height of a rank 3 tensor is its second dimension.
+    let max = placeholder
+        .clone()
+        .max_axis(Axis::Height, false)
+        .run_tensor();
+    assert_eq!(&[2, 4], max.shape());
+    assert_eq!(23.0, max[&[1, 3][..]]);
+
+    let argmax = placeholder.clone().argmax_dim(2).run_tensor();
+    assert!(Tensor::from_const(vec![2, 3], 3.0) == argmax);
+
+    assert_eq!(
+        Err(TensoError::DimOutOfRange {
+            op: "sum_dim",
+            dim: 3,
+            rank: 3,
+        }),
+        placeholder.sum_dim(3, false).try_run().map(|_| ())
+    );
+
+    let var = range(vec![2, 3, 4]).as_variable();
+    let mut result_op = var.mean_dim(2, false).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::from_const(6, 4, 0.25)],
+    });
+    result_op.add_to_optimizer(&mut optim);
+    optim.step();
+}
+
+#[test]
+fn graph_softmax() {
+    let placeholder = InputPlaceholder::with_value(Tensor::randn(vec![2, 2, 3], 0.0, 1.0));
+
+    let probs = placeholder.softmax(Axis::Width).run_tensor();
+    assert_eq!(&[2, 2, 3], probs.shape());
+    for index in 0..probs.lane_count(2) {
+        let total: f32 = probs.lane(2, index).iter().sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn graph_concat() {
+    let left = Tensor::from_const(vec![2, 1, 3], 1.0).as_variable();
+    let right = Tensor::from_const(vec![2, 2, 3], 2.0).as_variable();
+
+    let mut joined_op = Operation::concat(&[left.clone(), right.clone()], Axis::Height);
+    let joined = joined_op.run_tensor();
+    assert_eq!(&[2, 3, 3], joined.shape());
+    assert_eq!(1.0, joined[&[1, 0, 2][..]]);
+    assert_eq!(2.0, joined[&[1, 1, 2][..]]);
+
+    let mut result_op = joined_op.pow(2.0).sum();
+    result_op.run();
+    result_op.back();
+
+    let mut optim = RunningOptimizer::new(TestOptimizer {
+        expected_grads: vec![Matrix::from_const(2, 3, 2.0), Matrix::from_const(4, 3, 4.0)],
+    });
+    result_op.add_to_optimizer(&mut optim);
+    optim.step();
+
+    let invalid = Tensor::zeros(vec![3, 2, 3]).as_variable();
+    assert_eq!(
+        Err(TensoError::ShapeMismatch {
+            op: "concat",
+            left: vec![2, 1, 3],
+            right: vec![3, 2, 3],
+        }),
+        Operation::concat(&[left, invalid], Axis::Height)
+            .try_run()
+            .map(|_| ())
+    );
+}
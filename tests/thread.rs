@@ -0,0 +1,93 @@
+use std::thread;
+
+use tenso_rs::{
+    self,
+    matrix::Matrix,
+    operation::{input::InputPlaceholder, Operation},
+    optim::{sgd::SGDOptimizerRunner, Optimizer, RunningOptimizer},
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<Operation>();
+    assert_send_sync::<RunningOptimizer<SGDOptimizerRunner>>();
+}
+
+#[test]
+fn train_in_background() {
+    let weights = Matrix::new(1, 2, vec![0.5, -0.5]).as_variable();
+    let mut input_ph = InputPlaceholder::with_value(Matrix::new(2, 1, vec![1.0, 2.0]));
+    let mut target_ph = InputPlaceholder::with_value(Matrix::new(1, 1, vec![3.0]));
+
+    let mut loss_op = (weights.clone().mmul(input_ph.clone()) - target_ph.clone())
+        .pow(2.0)
+        .sum();
+
+    let initial_loss = loss_op.run()[0][0];
+    let trained_loss = thread::spawn(move || {
+        let mut optim = RunningOptimizer::new(SGDOptimizerRunner::new(0.05));
+        loss_op.add_to_optimizer(&mut optim);
+
+        for _ in 0..50 {
+            input_ph.set_input(Matrix::new(2, 1, vec![1.0, 2.0]));
+            target_ph.set_input(Matrix::new(1, 1, vec![3.0]));
+
+            loss_op.run();
+            loss_op.back();
+            optim.step();
+            optim.zero_grad();
+        }
+
+        loss_op.run()[0][0]
+    })
+    .join()
+    .unwrap();
+
+    assert!(trained_loss < initial_loss * 1e-3);
+
+    // The variable shared with the background thread holds the trained weights.
+    let weights = weights.get_output();
+    assert!((weights[0][0] + 2.0 * weights[0][1] - 3.0).abs() < 1e-2);
+}
+
+#[test]
+fn shared_inference() {
+    let weights = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]).as_variable();
+
+    let workers: Vec<_> = (0..4)
+        .map(|i| {
+            let weights = weights.clone();
+            thread::spawn(move || {
+                let input_ph = InputPlaceholder::new();
+                let mut output_op = weights.mmul(input_ph.clone()).sum();
+
+                let mut outputs = Vec::new();
+                for j in 0..100 {
+                    let mut input_ph = input_ph.clone();
+                    input_ph.set_input(Matrix::new(2, 1, vec![i as f32, j as f32]));
+                    outputs.push(output_op.run()[0][0]);
+                }
+                outputs
+            })
+        })
+        .collect();
+
+    for (i, worker) in workers.into_iter().enumerate() {
+        for (j, output) in worker.join().unwrap().into_iter().enumerate() {
+            assert_eq!(4.0 * i as f32 + 6.0 * j as f32, output);
+        }
+    }
+}
+
+#[test]
+fn same_input_twice() {
+    let var = Matrix::new(1, 2, vec![2.0, 3.0]).as_variable();
+
+    let mut square_op = (var.clone() * var.clone()).sum();
+    let mut custom_op = Operation::add_n(&[var.clone(), var.clone(), var]);
+
+    assert_eq!(13.0, square_op.run()[0][0]);
+    assert!(Matrix::new(1, 2, vec![6.0, 9.0]) == custom_op.run());
+}